@@ -0,0 +1,71 @@
+//! Generates the list of solutions that are linked into the main binary.
+//! Every `src/bin/<day>.rs` and `src/bin/<year>-<day>.rs` file is included as a module and its
+//! registry entry is collected.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut stems: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let stem = name.strip_suffix(".rs")?;
+                    is_solution(stem).then(|| stem.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    stems.sort();
+
+    let mut code = String::new();
+
+    for stem in &stems {
+        let path = bin_dir.join(format!("{stem}.rs"));
+        code.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod {};\n",
+            path.display().to_string(),
+            module_name(stem)
+        ));
+    }
+
+    code.push_str(
+        "\npub fn registry() -> advent_of_code::template::registry::Registry {\n    advent_of_code::template::registry::Registry::new(vec![\n",
+    );
+
+    for stem in &stems {
+        code.push_str(&format!(
+            "        {}::registry_entry(),\n",
+            module_name(stem)
+        ));
+    }
+
+    code.push_str("    ])\n}\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), code).unwrap();
+}
+
+fn is_digits(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Solutions are named `<day>` or `<year>-<day>`, e.g. `05` or `2022-05`.
+fn is_solution(stem: &str) -> bool {
+    match stem.split_once('-') {
+        Some((year, day)) => is_digits(year, 4) && is_digits(day, 2),
+        None => is_digits(stem, 2),
+    }
+}
+
+fn module_name(stem: &str) -> String {
+    match stem.split_once('-') {
+        Some((year, day)) => format!("year_{year}_day_{day}"),
+        None => format!("day_{stem}"),
+    }
+}
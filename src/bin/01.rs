@@ -80,19 +80,17 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::examples;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_custom_file(
-            "examples",
-            "day01_part_a.txt",
-        ));
+        let result = part_one(&examples::read(DAY, 1, None));
         assert_eq!(result, Some(142));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&examples::read(DAY, 2, None));
         assert_eq!(result, Some(281));
     }
 
@@ -1,33 +1,33 @@
 advent_of_code::solution!(4);
 
-use chumsky::prelude::*;
+use advent_of_code::{parse, parse_lines, PuzzleError};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-fn integer_parser() -> impl Parser<char, u32, Error = Simple<char>> {
-    text::int(10).map(|s: String| s.parse::<u32>().unwrap())
-}
+// Parse a line like "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+fn parse_card(line: &str) -> Result<Card, PuzzleError> {
+    let bar = line.find('|').ok_or_else(|| {
+        PuzzleError::at_offset(line, line.len(), "expected a `|` between the numbers")
+    })?;
+    let (label, winning_numbers) = parse::key_values(&line[..bar])?;
 
-fn integer_list_parser() -> impl Parser<char, Vec<u32>, Error = Simple<char>> {
-    integer_parser().separated_by(text::whitespace())
-}
+    let ids = match label.strip_prefix("Card") {
+        Some(_) => parse::list_from(label, "Card".len())?,
+        None => Vec::new(),
+    };
+    let [id] = ids[..] else {
+        return Err(PuzzleError::at_offset(
+            line,
+            0,
+            "expected a `Card <id>:` label",
+        ));
+    };
 
-fn line_to_card_parser() -> impl Parser<char, Card, Error = Simple<char>> {
-    just("Card")
-        .ignore_then(text::whitespace())
-        .ignore_then(integer_parser())
-        .then_ignore(just(':'))
-        .then_ignore(text::whitespace())
-        .then(integer_list_parser())
-        .then_ignore(text::whitespace())
-        .then_ignore(just('|'))
-        .then_ignore(text::whitespace())
-        .then(integer_list_parser())
-        .map(|((card_number, first_list), second_list)| Card {
-            id: card_number,
-            winning_numbers: HashSet::from_iter(first_list),
-            numbers: HashSet::from_iter(second_list),
-        })
+    Ok(Card {
+        id,
+        winning_numbers: HashSet::from_iter(winning_numbers),
+        numbers: HashSet::from_iter(parse::list_from(line, bar + 1)?),
+    })
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -37,17 +37,8 @@ struct Card {
     numbers: HashSet<u32>,
 }
 
-fn cards_from_input(input: &str) -> nom::IResult<&str, Vec<Card>> {
-    let parser = line_to_card_parser();
-    let cards = input
-        .lines()
-        .map(|line| parser.parse(line).unwrap())
-        .collect();
-    Ok(("", cards))
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards = cards_from_input(input).unwrap().1;
+pub fn part_one(input: &str) -> Result<u32, PuzzleError> {
+    let cards = parse_lines(input, parse_card)?;
     let result = cards
         .iter()
         .map(|card| {
@@ -61,11 +52,11 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
         })
         .sum::<u32>();
-    Some(result)
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = cards_from_input(input).unwrap().1;
+pub fn part_two(input: &str) -> Result<u32, PuzzleError> {
+    let cards = parse_lines(input, parse_card)?;
     let num_cards = cards.len();
     let mut card_counts: HashMap<u32, u32> =
         HashMap::from_iter(cards.iter().map(|card| (card.id, 1)));
@@ -80,7 +71,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
     let result = card_counts.values().sum::<u32>();
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -90,54 +81,49 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result.unwrap(), 13);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result.unwrap(), 30);
     }
 
     #[test]
     fn test_parse_line_to_card() {
-        let result =
-            line_to_card_parser().parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
-        if let Ok(card) = result {
-            assert_eq!(
-                card,
-                Card {
-                    id: 1,
-                    winning_numbers: HashSet::from_iter(vec![41, 48, 83, 86, 17]),
-                    numbers: HashSet::from_iter(vec![83, 86, 6, 31, 17, 9, 48, 53])
-                }
-            );
-        } else {
-            panic!("Failed to parse card");
-        }
+        let card = parse_card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        assert_eq!(
+            card,
+            Card {
+                id: 1,
+                winning_numbers: HashSet::from_iter(vec![41, 48, 83, 86, 17]),
+                numbers: HashSet::from_iter(vec![83, 86, 6, 31, 17, 9, 48, 53])
+            }
+        );
     }
 
     #[test]
-    fn test_card_parse() {
-        let parser = just("Card ")
-            .ignore_then(integer_parser())
-            .then_ignore(just(":"))
-            .then_ignore(text::whitespace())
-            .then(integer_list_parser())
-            .then_ignore(text::whitespace())
-            .then_ignore(just("|"))
-            .then_ignore(text::whitespace())
-            .then(integer_list_parser())
-            .map(|((card_number, first_list), second_list)| (card_number, first_list, second_list));
-        let result = parser.parse("Card 1:  1  2 3 | 5 6 7");
-        assert_eq!(result, Ok((1, vec![1, 2, 3], vec![5, 6, 7])));
+    fn test_card_parse_errors() {
+        let error = part_one("Card 1:  1  2 3 | 5 6 7\nCard 2:  1  x 3 | 5 6 7").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(13));
+
+        let error = parse_card("Card 1:  1  2 3 | 5 6 -7").unwrap_err();
+        assert_eq!(error.column(), Some(23));
+
+        let error = parse_card("Cart 1:  1  2 3 | 5 6 7").unwrap_err();
+        assert_eq!(error.message(), "expected a `Card <id>:` label");
+
+        let error = parse_card("Card 1:  1  2 3").unwrap_err();
+        assert_eq!(error.message(), "expected a `|` between the numbers");
     }
 
     #[rstest::rstest]
     #[case("Card 1:  1  2 3 | 5 6 7")]
     #[case( "Card   4:  2  4 14 88 19 99  3 84  1 77 | 61 59 53 88 31 72 62 11  9 50 46 42 66 79 55 87 30 63 37 69 95 83 15 21 35")]
     fn test_can_parse_line(#[case] line: &str) {
-        let result = line_to_card_parser().parse(line);
+        let result = parse_card(line);
         assert!(result.is_ok());
     }
 }
@@ -1,14 +1,8 @@
 advent_of_code::solution!(5);
 
-use chumsky::prelude::*;
+use std::ops::Range;
 
-fn integer_parser() -> impl Parser<char, u64, Error = Simple<char>> {
-    text::int(10).map(|s: String| s.parse::<u64>().unwrap())
-}
-
-fn integer_list_parser() -> impl Parser<char, Vec<u64>, Error = Simple<char>> {
-    integer_parser().separated_by(text::whitespace())
-}
+use advent_of_code::{parse, parse_lines, IntervalSet, PuzzleError};
 
 /// Structure for representing a range of mappings from source to destination
 #[derive(Debug, PartialEq)]
@@ -26,46 +20,27 @@ impl MapSpan {
             None
         }
     }
-    fn source(&self, target: u64) -> Option<u64> {
-        if target >= self.target && target < self.target + self.range {
-            Some(self.source + (target - self.target))
-        } else {
-            None
-        }
-    }
 }
 
-fn map_span_parser() -> impl Parser<char, MapSpan, Error = Simple<char>> {
-    integer_parser()
-        .then_ignore(text::whitespace())
-        .then(integer_parser())
-        .then_ignore(text::whitespace())
-        .then(integer_parser())
-        .map(|((target, source), range)| MapSpan {
-            source,
-            target,
-            range,
-        })
+// Parse a line like "50 98 2": the target, the source and the length of the span
+fn parse_map_span(line: &str) -> Result<MapSpan, PuzzleError> {
+    let [target, source, range] = parse::list(line)?[..] else {
+        return Err(PuzzleError::new("expected a target, a source and a range"));
+    };
+
+    Ok(MapSpan {
+        source,
+        target,
+        range,
+    })
 }
 
 #[derive(Debug)]
 struct GardenMapping {
-    name: String,
     spans: Vec<MapSpan>,
 }
 
 impl GardenMapping {
-    fn new() -> Self {
-        Self {
-            name: String::new(),
-            spans: Vec::new(),
-        }
-    }
-
-    fn add_span(&mut self, map_span: MapSpan) {
-        self.spans.push(map_span);
-    }
-
     fn destination(&self, source: u64) -> u64 {
         for span in &self.spans {
             if let Some(destination) = span.destination(source) {
@@ -75,62 +50,38 @@ impl GardenMapping {
         // If we don't have an explicit mapping, the target is the same as the source
         source
     }
-    fn source(&self, target: u64) -> u64 {
-        for span in self.spans.iter() {
-            if let Some(source) = span.source(target) {
-                return source;
-            }
-        }
-        // If we don't have an explicit mapping, the target is the same as the source
-        target
-    }
-}
 
-struct SeedRanges {
-    ranges: Vec<(u64, u64)>,
-}
-
-impl SeedRanges {
-    fn contains(&self, value: u64) -> bool {
-        for (start, length) in &self.ranges {
-            if value >= *start && value < *start + *length {
-                return true;
-            }
-        }
-        false
+    /// The spans as pieces of a piecewise-linear map from source to destination ranges
+    fn pieces(&self) -> Vec<(Range<u64>, u64)> {
+        self.spans
+            .iter()
+            .map(|span| (span.source..span.source + span.range, span.target))
+            .collect()
     }
 }
 
-fn parse_input_maps(input: &str) -> (Vec<u64>, Vec<GardenMapping>) {
-    let mut mappings = Vec::new();
-    let mut mapping = GardenMapping::new();
-
-    let mut lines = input.lines();
-    let seeds_parser = just("seeds: ").then(integer_list_parser());
-    let seeds_line = lines.next().unwrap();
-    let seeds = seeds_parser.parse(seeds_line).unwrap().1;
-
-    lines.next();
-
-    for line in lines {
-        if line.is_empty() {
-            mappings.push(mapping);
-            mapping = GardenMapping::new();
-        } else if line.contains("map:") {
-            mapping.name = line.to_string();
-        } else {
-            let map_span = map_span_parser().parse(line).unwrap();
-            mapping.add_span(map_span);
-        }
-    }
-
-    mappings.push(mapping);
+// Parse the seeds line, e.g. "seeds: 79 14 55 13", and the sections of the mappings below it
+fn parse_input_maps(input: &str) -> Result<(Vec<u64>, Vec<GardenMapping>), PuzzleError> {
+    let mut blocks = parse::blocks(input);
+    let seeds = blocks
+        .next()
+        .ok_or_else(|| PuzzleError::new("expected a `seeds:` line"))?
+        .parse(parse::key_values::<u64>)?
+        .1;
+
+    let mappings = blocks
+        .map(|block| {
+            block.section(|_, body| {
+                parse_lines(body, parse_map_span).map(|spans| GardenMapping { spans })
+            })
+        })
+        .collect::<Result<_, _>>()?;
 
-    (seeds, mappings)
+    Ok((seeds, mappings))
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (seeds, mappings) = parse_input_maps(input);
+pub fn part_one(input: &str) -> Result<u64, PuzzleError> {
+    let (seeds, mappings) = parse_input_maps(input)?;
     let result = seeds
         .iter()
         .map(|seed| {
@@ -142,27 +93,24 @@ pub fn part_one(input: &str) -> Option<u64> {
             source
         })
         .min();
-    Some(result.unwrap())
+    result.ok_or_else(|| PuzzleError::new("expected at least one seed"))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (seeds, mappings) = parse_input_maps(input);
-    let seed_ranges = SeedRanges {
-        ranges: seeds.chunks(2).map(|chunk| (chunk[0], chunk[1])).collect(),
-    };
+pub fn part_two(input: &str) -> Result<u64, PuzzleError> {
+    let (seeds, mappings) = parse_input_maps(input)?;
+    let mut ranges: IntervalSet<u64> = seeds
+        .chunks(2)
+        .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+        .collect();
 
-    // It's too slow to try all the seeds. So we work back from locations until we find
-    // the lowest location number that routes back to a seed.
-    let result = (1..).find(|&seed| {
-        let mut destination = seed;
-        for mapping in mappings.iter().rev() {
-            let source = mapping.source(destination);
-            destination = source;
-        }
-        seed_ranges.contains(destination)
-    });
+    // Map whole ranges of seeds at once, splitting them where the spans of a mapping end
+    for mapping in &mappings {
+        ranges = ranges.map_pieces(&mapping.pieces());
+    }
 
-    Some(result.unwrap())
+    ranges
+        .min()
+        .ok_or_else(|| PuzzleError::new("expected at least one seed"))
 }
 
 #[cfg(test)]
@@ -172,13 +120,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        assert_eq!(result.unwrap(), 35);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result.unwrap(), 46);
     }
 
     #[test]
@@ -204,10 +152,9 @@ mod tests {
     #[test]
     fn test_parse_input() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (seeds, mappings) = parse_input_maps(&input);
+        let (seeds, mappings) = parse_input_maps(&input).unwrap();
         assert_eq!(seeds, vec![79, 14, 55, 13]);
         assert_eq!(mappings.len(), 7);
-        assert_eq!(mappings[0].name, "seed-to-soil map:");
         assert_eq!(mappings[0].spans.len(), 2);
         assert_eq!(mappings[0].spans[0].target, 50);
         assert_eq!(mappings[0].spans[0].source, 98);
@@ -215,7 +162,6 @@ mod tests {
         assert_eq!(mappings[0].spans[1].target, 52);
         assert_eq!(mappings[0].spans[1].source, 50);
         assert_eq!(mappings[0].spans[1].range, 48);
-        assert_eq!(mappings[6].name, "humidity-to-location map:");
         assert_eq!(mappings[6].spans.len(), 2);
         assert_eq!(mappings[6].spans[0].target, 60);
         assert_eq!(mappings[6].spans[0].source, 56);
@@ -226,15 +172,23 @@ mod tests {
     }
 
     #[test]
-    fn test_reverse_mapping() {
+    fn test_parse_errors() {
+        let error = parse_input_maps("seeds: 1 2\n\na map:\n1 2 3\n4 5\n").unwrap_err();
+        assert_eq!(error.line(), Some(5));
+        assert_eq!(error.message(), "expected a target, a source and a range");
+
+        let error = part_one("seeds: 1 x\n").unwrap_err();
+        assert_eq!(error.line(), Some(1));
+        assert_eq!(error.column(), Some(10));
+    }
+
+    #[test]
+    fn test_range_mapping() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (_seeds, mappings) = parse_input_maps(&input);
+        let (_seeds, mappings) = parse_input_maps(&input).unwrap();
 
-        let mut destination = 35;
-        for mapping in mappings.iter().rev() {
-            let source = mapping.source(destination);
-            destination = source;
-        }
-        assert_eq!(destination, 13)
+        let seeds = IntervalSet::from(79..93);
+        let soil = seeds.map_pieces(&mappings[0].pieces());
+        assert_eq!(soil, IntervalSet::from(81..95));
     }
 }
@@ -1,34 +1,32 @@
 advent_of_code::solution!(6);
 
-use chumsky::prelude::*;
-
-fn integer_parser() -> impl Parser<char, u64, Error = Simple<char>> {
-    text::int(10).map(|s: String| s.parse::<u64>().unwrap())
+use std::error::Error;
+use std::str::FromStr;
+
+use advent_of_code::{parse, parse_lines, PuzzleError};
+
+/// Parses the values of the `Time:` and `Distance:` lines.
+fn parse_times_and_distances<T>(input: &str) -> Result<(Vec<T>, Vec<T>), PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let mut rows = parse_lines(input, parse::key_values::<T>)?.into_iter();
+
+    match (rows.next(), rows.next()) {
+        (Some(("Time", times)), Some(("Distance", distances))) => Ok((times, distances)),
+        _ => Err(PuzzleError::new("expected `Time:` and `Distance:` lines")),
+    }
 }
 
-fn integer_list_parser() -> impl Parser<char, Vec<u64>, Error = Simple<char>> {
-    integer_parser().separated_by(text::whitespace())
-}
+fn parse_races(input: &str) -> Result<Vec<Race>, PuzzleError> {
+    let (times, distances) = parse_times_and_distances(input)?;
 
-fn parse_races(input: &str) -> Vec<Race> {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let times = just("Time:")
-        .ignore_then(text::whitespace())
-        .ignore_then(integer_list_parser())
-        .parse(lines[0])
-        .unwrap();
-
-    let distances = just("Distance:")
-        .ignore_then(text::whitespace())
-        .ignore_then(integer_list_parser())
-        .parse(lines[1])
-        .unwrap();
-
-    times
+    Ok(times
         .into_iter()
         .zip(distances)
         .map(|(time, distance)| Race { time, distance })
-        .collect::<Vec<Race>>()
+        .collect())
 }
 
 struct Race {
@@ -60,39 +58,23 @@ fn _distance_travelled(time: u64, held: u64) -> u64 {
     held * (time - held)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let races = parse_races(input);
+pub fn part_one(input: &str) -> Result<u32, PuzzleError> {
+    let races = parse_races(input)?;
     let score = races
         .into_iter()
         .map(|race| race.permutations_that_beat_distance())
         .product::<u64>();
-    Some(score as u32)
+    Ok(score as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let time = just("Time:")
-        .ignore_then(integer_parser())
-        .parse(
-            lines[0]
-                .chars()
-                .filter(|c| !c.is_whitespace())
-                .collect::<String>(),
-        )
-        .unwrap();
-
-    let distance = just("Distance:")
-        .ignore_then(integer_parser())
-        .parse(
-            lines[1]
-                .chars()
-                .filter(|c| !c.is_whitespace())
-                .collect::<String>(),
-        )
-        .unwrap();
+pub fn part_two(input: &str) -> Result<u32, PuzzleError> {
+    // the spaces between the numbers are bad kerning, read each line as a single number
+    let (times, distances) = parse_times_and_distances::<String>(input)?;
+    let time = parse::int(&times.concat())?;
+    let distance = parse::int(&distances.concat())?;
 
     let race = Race { time, distance };
-    Some(race.permutations_that_beat_distance() as u32)
+    Ok(race.permutations_that_beat_distance() as u32)
 }
 
 #[cfg(test)]
@@ -102,13 +84,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result.unwrap(), 288);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        assert_eq!(result.unwrap(), 71503);
     }
 
     #[rstest::rstest]
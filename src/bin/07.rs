@@ -1,9 +1,12 @@
 advent_of_code::solution!(7);
 
+use advent_of_code::{parse_lines, PuzzleError};
+
 // Since the code structures are similar but the logic is different between part 1 and part 2
 // I've split the code into two modules so I can define some of the types differently
 // There is a lot of duplication though, so there's probably a cleaner way to handle this
 mod part_one {
+    use advent_of_code::PuzzleError;
     use std::collections::HashMap;
     #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Clone, Copy, Ord)]
     pub enum Card {
@@ -23,8 +26,8 @@ mod part_one {
     }
 
     impl Card {
-        pub fn from_char(c: char) -> Self {
-            match c {
+        pub fn from_char(c: char) -> Result<Self, PuzzleError> {
+            let card = match c {
                 '2' => Card::C2,
                 '3' => Card::C3,
                 '4' => Card::C4,
@@ -38,8 +41,9 @@ mod part_one {
                 'Q' => Card::CQ,
                 'K' => Card::CK,
                 'A' => Card::CA,
-                _ => panic!("Unknown Card"),
-            }
+                _ => return Err(PuzzleError::new(format!("unknown card '{c}'"))),
+            };
+            Ok(card)
         }
     }
 
@@ -54,8 +58,12 @@ mod part_one {
         result
     }
 
-    pub fn cards_from_str(input: &str) -> Vec<Card> {
-        input.chars().map(Card::from_char).collect()
+    pub fn cards_from_str(input: &str) -> Result<Vec<Card>, PuzzleError> {
+        input
+            .chars()
+            .enumerate()
+            .map(|(i, c)| Card::from_char(c).map_err(|e| e.at_column(i + 1)))
+            .collect()
     }
 
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -73,7 +81,7 @@ mod part_one {
         fn from_cards(cards: Vec<Card>) -> Self {
             let counts = card_counts(cards);
             let mut counts: Vec<(Card, u8)> = counts.into_iter().collect();
-            counts.sort_by(|a, b| b.1.cmp(&a.1));
+            counts.sort_by_key(|c| std::cmp::Reverse(c.1));
             let (_card, count) = &counts[0];
             match count {
                 5 => HandType::FiveOfAKind,
@@ -105,9 +113,9 @@ mod part_one {
     }
 
     impl Hand {
-        pub fn from_word(word: &str) -> Self {
-            let cards = cards_from_str(word);
-            Hand::from_cards(cards.clone())
+        pub fn from_word(word: &str) -> Result<Self, PuzzleError> {
+            let cards = cards_from_str(word)?;
+            Ok(Hand::from_cards(cards.clone()))
         }
         fn from_cards(cards: Vec<Card>) -> Self {
             let hand_type = HandType::from_cards(cards.clone());
@@ -117,6 +125,7 @@ mod part_one {
 }
 
 mod part_two {
+    use advent_of_code::PuzzleError;
     use std::collections::HashMap;
     #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Clone, Copy, Ord)]
     pub enum Card {
@@ -136,8 +145,8 @@ mod part_two {
     }
 
     impl Card {
-        pub fn from_char(c: char) -> Self {
-            match c {
+        pub fn from_char(c: char) -> Result<Self, PuzzleError> {
+            let card = match c {
                 'J' => Card::CJ,
                 '2' => Card::C2,
                 '3' => Card::C3,
@@ -151,8 +160,9 @@ mod part_two {
                 'Q' => Card::CQ,
                 'K' => Card::CK,
                 'A' => Card::CA,
-                _ => panic!("Unknown Card"),
-            }
+                _ => return Err(PuzzleError::new(format!("unknown card '{c}'"))),
+            };
+            Ok(card)
         }
     }
 
@@ -167,8 +177,12 @@ mod part_two {
         result
     }
 
-    pub fn cards_from_str(input: &str) -> Vec<Card> {
-        input.chars().map(Card::from_char).collect()
+    pub fn cards_from_str(input: &str) -> Result<Vec<Card>, PuzzleError> {
+        input
+            .chars()
+            .enumerate()
+            .map(|(i, c)| Card::from_char(c).map_err(|e| e.at_column(i + 1)))
+            .collect()
     }
 
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -191,7 +205,7 @@ mod part_two {
             let counts = card_counts(cards.clone());
             let num_jokers = *counts.get(&Card::CJ).unwrap_or(&0);
             let mut counts: Vec<(Card, u8)> = counts.into_iter().collect();
-            counts.sort_by(|a, b| b.1.cmp(&a.1));
+            counts.sort_by_key(|c| std::cmp::Reverse(c.1));
             let (_, count) = &counts[0];
             match count {
                 5 => HandType::FiveOfAKind,
@@ -269,9 +283,9 @@ mod part_two {
     }
 
     impl Hand {
-        pub fn from_word(word: &str) -> Self {
-            let cards = cards_from_str(word);
-            Hand::from_cards(cards.clone())
+        pub fn from_word(word: &str) -> Result<Self, PuzzleError> {
+            let cards = cards_from_str(word)?;
+            Ok(Hand::from_cards(cards.clone()))
         }
         fn from_cards(cards: Vec<Card>) -> Self {
             let hand_type = HandType::from_cards(cards.clone());
@@ -280,51 +294,45 @@ mod part_two {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, PuzzleError> {
     use part_one::Hand;
-    // split each line into two words (on whitespace) and collect into a vector
-    let mut hands = input
-        .lines()
-        .map(|line| {
-            let words = line.split_whitespace().collect::<Vec<&str>>();
-            (Hand::from_word(words[0]), words[1].parse::<u32>().unwrap())
-        })
-        .collect::<Vec<(Hand, u32)>>();
+    // split each line into two words (on whitespace), errors point at the offending line
+    let mut hands = parse_lines(input, |line| {
+        let (cards, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| PuzzleError::new("expected a hand and a bid"))?;
+        Ok::<_, PuzzleError>((Hand::from_word(cards)?, bid.parse::<u32>()?))
+    })?;
 
     // Sort the hands by the first element (the hand) and then by the second element (the score)
     hands.sort_by(|a, b| a.0.cmp(&b.0));
 
-    Some(
-        hands
-            .iter()
-            .enumerate()
-            .map(|(i, (_, score))| score * (i + 1) as u32)
-            .sum::<u32>(),
-    )
+    Ok(hands
+        .iter()
+        .enumerate()
+        .map(|(i, (_, score))| score * (i + 1) as u32)
+        .sum::<u32>())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, PuzzleError> {
     use part_two::Hand;
-    // split each line into two words (on whitespace) and collect into a vector
-    let mut hands = input
-        .lines()
-        .map(|line| {
-            let words = line.split_whitespace().collect::<Vec<&str>>();
-            (Hand::from_word(words[0]), words[1].parse::<u32>().unwrap())
-        })
-        .collect::<Vec<(Hand, u32)>>();
+    // split each line into two words (on whitespace), errors point at the offending line
+    let mut hands = parse_lines(input, |line| {
+        let (cards, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| PuzzleError::new("expected a hand and a bid"))?;
+        Ok::<_, PuzzleError>((Hand::from_word(cards)?, bid.parse::<u32>()?))
+    })?;
 
     // Sort the hands by the first element (the hand) and then by the second element (the score)
     hands.sort_by(|a, b| a.0.cmp(&b.0));
 
-    Some(
-        hands
-            .iter()
-            .enumerate()
-            // .inspect(|(i, (hand, bet))| println!("{}: {:?}, bet:{}", i, hand, bet))
-            .map(|(i, (_, score))| score * (i + 1) as u32)
-            .sum::<u32>(),
-    )
+    Ok(hands
+        .iter()
+        .enumerate()
+        // .inspect(|(i, (hand, bet))| println!("{}: {:?}, bet:{}", i, hand, bet))
+        .map(|(i, (_, score))| score * (i + 1) as u32)
+        .sum::<u32>())
 }
 
 #[cfg(test)]
@@ -334,13 +342,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
+        assert_eq!(result.unwrap(), 6440);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
+        assert_eq!(result.unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_unknown_card() {
+        let error = part_one("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(4));
+        assert_eq!(error.message(), "unknown card 'X'");
     }
 
     #[rstest::rstest]
@@ -352,8 +368,8 @@ mod tests {
     #[case('2', 'A')]
     fn test_card_order(#[case] c1: char, #[case] c2: char) {
         use part_one::Card;
-        let c1 = Card::from_char(c1);
-        let c2 = Card::from_char(c2);
+        let c1 = Card::from_char(c1).unwrap();
+        let c2 = Card::from_char(c2).unwrap();
         assert!(c2 > c1);
     }
 
@@ -366,8 +382,8 @@ mod tests {
     #[case('2', 'A')]
     fn test_card_order_part_2(#[case] c1: char, #[case] c2: char) {
         use part_two::Card;
-        let c1 = Card::from_char(c1);
-        let c2 = Card::from_char(c2);
+        let c1 = Card::from_char(c1).unwrap();
+        let c2 = Card::from_char(c2).unwrap();
         assert!(c2 > c1);
     }
 
@@ -376,19 +392,19 @@ mod tests {
     #[case('T', 'T')]
     fn test_card_eq(#[case] c1: char, #[case] c2: char) {
         use part_one::Card;
-        let c1 = Card::from_char(c1);
-        let c2 = Card::from_char(c2);
+        let c1 = Card::from_char(c1).unwrap();
+        let c2 = Card::from_char(c2).unwrap();
         assert!(c2 == c1);
     }
 
     #[test]
     fn test_counts() {
         use part_one::{card_counts, cards_from_str, Card};
-        let cards = cards_from_str("234233");
+        let cards = cards_from_str("234233").unwrap();
         let counts = card_counts(cards);
-        assert_eq!(counts[&Card::from_char('2')], 2);
-        assert_eq!(counts[&Card::from_char('3')], 3);
-        assert_eq!(counts[&Card::from_char('4')], 1);
+        assert_eq!(counts[&Card::from_char('2').unwrap()], 2);
+        assert_eq!(counts[&Card::from_char('3').unwrap()], 3);
+        assert_eq!(counts[&Card::from_char('4').unwrap()], 1);
     }
     // There were lots of edge cases in the hand type logic, so I've tried to test some of the
     // ones I seemed to be getting wrong answers for here.
@@ -402,7 +418,7 @@ mod tests {
     #[case("JJJJJ", part_two::HandType::FiveOfAKind)]
     fn test_hand_type_part_2(#[case] input: &str, #[case] expected: part_two::HandType) {
         use part_two::Hand;
-        let hand = Hand::from_word(input);
+        let hand = Hand::from_word(input).unwrap();
         assert_eq!(hand.hand_type, expected);
     }
 }
@@ -1,10 +1,11 @@
 advent_of_code::solution!(8);
 
-extern crate num_integer;
-use num_integer::Integer;
-
 use std::collections::HashMap;
 
+use advent_of_code::cycle::{self, Cycle};
+use advent_of_code::math;
+use itertools::Itertools;
+
 use chumsky::prelude::*;
 
 #[derive(Clone, Copy, Debug)]
@@ -67,13 +68,6 @@ impl LocationMap {
         }
         self.current_locations = new_locations;
     }
-    fn locations_ending_with_char(&self, c: char) -> Vec<String> {
-        self.locations
-            .keys()
-            .filter(|id| id.ends_with(c))
-            .cloned()
-            .collect::<Vec<String>>()
-    }
 }
 
 fn parse_map(input: &[&str]) -> LocationMap {
@@ -111,33 +105,27 @@ fn parse_map(input: &[&str]) -> LocationMap {
     location_map
 }
 
-// Given a starting location, a map and a set of directions, find how many steps until we reach a finish
-fn cycle_for_start(start: &str, location_map: &LocationMap, directions: &[Direction]) -> u32 {
-    let mut step = 0;
-    let mut location = start;
-    for direction in directions.iter().cycle() {
-        location = match direction {
-            Direction::L => location_map
-                .locations
-                .get(location)
-                .unwrap()
-                .left
-                .as_ref()
-                .unwrap(),
-            Direction::R => location_map
-                .locations
-                .get(location)
-                .unwrap()
-                .right
-                .as_ref()
-                .unwrap(),
+// Follow the directions from a starting location until the walk loops. The walk loops once we are
+// back at a location at the same position in the directions, so that's the state we track.
+// Returns the loop and the location after every step up to its first repetition.
+fn walk_until_loop<'a>(
+    start: &'a str,
+    location_map: &'a LocationMap,
+    directions: &[Direction],
+) -> (Cycle, Vec<&'a str>) {
+    let (walk, states) = cycle::find((start, 0), |&(location, i)| {
+        let connection = &location_map.locations[location];
+        let next = match directions[i] {
+            Direction::L => connection.left.as_deref().unwrap(),
+            Direction::R => connection.right.as_deref().unwrap(),
         };
-        step += 1;
-        if location.ends_with('Z') {
-            break;
-        }
-    }
-    step
+        (next, (i + 1) % directions.len())
+    });
+
+    (
+        walk,
+        states.into_iter().map(|(location, _)| location).collect(),
+    )
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -175,63 +163,69 @@ pub fn part_two(input: &str) -> Option<u64> {
             _ => unreachable!(),
         })
         .collect::<Vec<Direction>>();
-    let mut location_map = parse_map(&lines[2..]);
-
-    // Unlike in part 1, we need to set up our starting locations to be all locations
-    // that end with 'A'
-    location_map.current_locations = location_map.locations_ending_with_char('A');
-
-    // Testing revealed that, despite the complexity of the map and directions,
-    // each starting location yields a constant number of steps to reach an end
-    // and continuing to repeat will not change the number of steps to get back to the end
-    // again.
-    // This makes our problem solvable as we can just find the period for each starting location
-    // to get to an end and then we have to find the lowest common multiple of those to get our
-    // solution.
-    // We make use of the num_integer crate to get the lcm function.
-    let steps: u64 = location_map
-        .current_locations
+    let location_map = parse_map(&lines[2..]);
+
+    // Unlike in part 1, we start at all locations that end with 'A'
+    let walks = location_map
+        .locations
+        .keys()
+        .filter(|id| id.ends_with('A'))
+        .map(|start| walk_until_loop(start, &location_map, &directions))
+        .collect::<Vec<_>>();
+
+    let is_end = |(walk, locations): &(Cycle, Vec<&str>), step: usize| {
+        locations[walk.equivalent_step(step)].ends_with('Z')
+    };
+
+    // Ends that are reached before a walk loops are only reached once, so all other walks have
+    // to be at an end at that very step.
+    let once = walks
         .iter()
-        .map(|location| cycle_for_start(location, &location_map, &directions) as u64)
-        .fold(1, |acc, x| acc.lcm(&x));
+        .flat_map(|(walk, locations)| {
+            (0..walk.prefix).filter(|&step| locations[step].ends_with('Z'))
+        })
+        .filter(|&step| walks.iter().all(|walk| is_end(walk, step)))
+        .min()
+        .map(|step| step as u64);
+
+    // Ends within the loop are reached every `period` steps, starting at their first visit. The
+    // walks meet at ends when one such sequence of each walk aligns. Testing revealed that every
+    // walk of the real input reaches one end exactly at multiples of its period, in which case
+    // this is the least common multiple of the periods.
+    let looping = walks
+        .iter()
+        .map(|(walk, locations)| {
+            (walk.prefix..walk.prefix + walk.period)
+                .filter(|&step| locations[step].ends_with('Z'))
+                .map(|step| (step as i64, walk.period as i64))
+                .collect::<Vec<_>>()
+        })
+        .multi_cartesian_product()
+        .filter_map(math::align)
+        .min()
+        .map(|step| step as u64);
 
-    Some(steps)
+    once.into_iter().chain(looping).min()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::examples;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-    #[test]
-    fn test_part_one_other() {
-        let input = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        let result = part_one(input);
-        assert_eq!(result, Some(6));
+        for example in examples::for_part(DAY, 1) {
+            let result = part_one(&example.input).map(|x| x.to_string());
+            assert_eq!(result.as_deref(), example.answer(1), "{}", example.name);
+        }
     }
 
     #[test]
     fn test_part_two() {
-        let input = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)
-";
-        let result = part_two(input);
-        assert_eq!(result, Some(6));
+        for example in examples::for_part(DAY, 2) {
+            let result = part_two(&example.input).map(|x| x.to_string());
+            assert_eq!(result.as_deref(), example.answer(2), "{}", example.name);
+        }
     }
 }
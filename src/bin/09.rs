@@ -1,25 +1,6 @@
 advent_of_code::solution!(9);
 
-use chumsky::prelude::*;
-
-// Get an integer, including negative numbers
-fn integer_parser() -> impl Parser<char, i32, Error = Simple<char>> {
-    // Parser for an optional negative sign
-    let sign = just('-').or_not();
-
-    // Parser for one or more digits
-    let digits = text::int(10);
-
-    // Combine the sign and digits to create an i32
-    sign.then(digits).map(|(sign, digits)| match sign {
-        Some(_) => -digits.parse::<i32>().unwrap(),
-        None => digits.parse::<i32>().unwrap(),
-    })
-}
-
-fn integer_list_parser() -> impl Parser<char, Vec<i32>, Error = Simple<char>> {
-    integer_parser().separated_by(text::whitespace())
-}
+use advent_of_code::{parse, parse_lines, PuzzleError};
 
 // Return the last value and a vector of the differences between each value
 fn gradient_and_last(data: Vec<i32>) -> (i32, Vec<i32>) {
@@ -64,20 +45,14 @@ fn pretrapolate(data: Vec<i32>) -> i32 {
     last_values.iter().rev().fold(0, |acc, &x| x - acc)
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let data = input
-        .lines()
-        .map(|line| integer_list_parser().parse(line).unwrap())
-        .collect::<Vec<_>>();
-    Some(data.into_iter().map(extrapolate).sum())
+pub fn part_one(input: &str) -> Result<i32, PuzzleError> {
+    let data = parse_lines(input, parse::list::<i32>)?;
+    Ok(data.into_iter().map(extrapolate).sum())
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let data = input
-        .lines()
-        .map(|line| integer_list_parser().parse(line).unwrap())
-        .collect::<Vec<_>>();
-    Some(data.into_iter().map(pretrapolate).sum())
+pub fn part_two(input: &str) -> Result<i32, PuzzleError> {
+    let data = parse_lines(input, parse::list::<i32>)?;
+    Ok(data.into_iter().map(pretrapolate).sum())
 }
 
 #[cfg(test)]
@@ -87,13 +62,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+        assert_eq!(result.unwrap(), 114);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result.unwrap(), 2);
     }
 
     #[test]
@@ -2,6 +2,8 @@ advent_of_code::solution!(10);
 
 use std::collections::HashMap;
 
+use advent_of_code::{polygon, Point};
+
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Copy, Clone)]
 struct Position {
     row: i32,
@@ -186,160 +188,6 @@ fn find_path(map: &HashMap<Position, Pipe>, start: &Position) -> Option<Vec<Posi
     None
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-struct Path {
-    path: Vec<Position>,
-    map: HashMap<Position, Pipe>,
-    min_row: i32,
-    max_row: i32,
-    min_column: i32,
-    max_column: i32,
-}
-
-impl Path {
-    fn new(path: Vec<Position>, map: HashMap<Position, Pipe>) -> Self {
-        let mut min_row = i32::max_value();
-        let mut max_row = i32::min_value();
-        let mut min_column = i32::max_value();
-        let mut max_column = i32::min_value();
-        for pos in &path {
-            if pos.row < min_row {
-                min_row = pos.row;
-            }
-            if pos.row > max_row {
-                max_row = pos.row;
-            }
-            if pos.column < min_column {
-                min_column = pos.column;
-            }
-            if pos.column > max_column {
-                max_column = pos.column;
-            }
-        }
-        Self {
-            path,
-            map,
-            min_row,
-            max_row,
-            min_column,
-            max_column,
-        }
-    }
-    // Return true if the given point is contained within the path
-    // We probe in each cardinal direction from the point and count the number of times we cross
-    // If the number of crossings in each direction is odd, we are inside the path.
-    fn point_contained(&self, pos: &Position) -> bool {
-        for direction in &[
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ] {
-            // If the point is part of the pipe, we can early return false
-            if self.path.iter().any(|p| p == pos) {
-                return false;
-            }
-            let crossings = self.crossings_from_point(*pos, *direction);
-            if crossings % 2 == 0 {
-                return false;
-            }
-        }
-        true
-    }
-
-    // Check the positon. If there is a pipe segment at that position, and it's
-    // in our main pipe loop (i.e. not just a random pipe segment somewhere else),
-    // return the pipe type.
-    // Else None
-    fn pipe_type_at_position(&self, pos: &Position) -> Option<PipeType> {
-        let pos_on_path = self.path.iter().find(|p| *p == pos);
-        if pos_on_path.is_some() {
-            return Some(self.map.get(pos)?.pipe_type);
-        }
-        None
-    }
-
-    fn crossings_from_point(&self, position: Position, direction: Direction) -> u32 {
-        let mut crossings = Vec::new();
-        let mut position = position;
-
-        let (row_delta, column_delta) = match direction {
-            Direction::North => (-1, 0),
-            Direction::South => (1, 0),
-            Direction::East => (0, 1),
-            Direction::West => (0, -1),
-        };
-
-        while position.row >= self.min_row
-            && position.row <= self.max_row
-            && position.column >= self.min_column
-            && position.column <= self.max_column
-        {
-            position.row += row_delta;
-            position.column += column_delta;
-            if let Some(pipe_type) = self.pipe_type_at_position(&position) {
-                crossings.push(pipe_type);
-            }
-        }
-
-        // Now count up the numbers of N, E, S and W connections within the pipes we cross
-        let (mut north, mut south, mut east, mut west) = (0, 0, 0, 0);
-        for p in crossings {
-            match p {
-                PipeType::NorthSouth => {
-                    north += 1;
-                    south += 1;
-                }
-                PipeType::EastWest => {
-                    east += 1;
-                    west += 1;
-                }
-                PipeType::NorthEast => {
-                    north += 1;
-                    east += 1;
-                }
-                PipeType::NorthWest => {
-                    north += 1;
-                    west += 1;
-                }
-                PipeType::SouthEast => {
-                    south += 1;
-                    east += 1;
-                }
-                PipeType::SouthWest => {
-                    south += 1;
-                    west += 1;
-                }
-                PipeType::Start => {}
-            }
-        }
-        match direction {
-            Direction::North | Direction::South => east.min(west),
-            Direction::East | Direction::West => north.min(south),
-        }
-    }
-
-    fn bounding_points(&self) -> Vec<Position> {
-        let mut points = Vec::new();
-        for row in self.min_row..=self.max_row {
-            for column in self.min_column..=self.max_column {
-                let pos = Position { row, column };
-                if self.point_contained(&pos) {
-                    points.push(pos);
-                }
-            }
-        }
-        points
-    }
-}
-
 fn find_path_from_start(
     map: &HashMap<Position, Pipe>,
     previous: Position,
@@ -354,6 +202,10 @@ fn find_path_from_start(
             break;
         }
         let next = next_pipe(map, &current, &previous)?;
+        // The pipe has to connect back to where we came from, else we've left the loop
+        if next_pipe(map, &current, &next)? != previous {
+            return None;
+        }
         previous = current;
         current = next;
     }
@@ -371,9 +223,13 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let (map, start) = input_to_map(input);
     let path = find_path(&map, &start)?;
-    let map_path = Path::new(path, map);
-    let bounding_points = map_path.bounding_points();
-    Some(bounding_points.len() as u32)
+    // Every pipe of the loop is a lattice point on its boundary, so the enclosed tiles are the
+    // lattice points strictly inside it.
+    let vertices = path
+        .iter()
+        .map(|pos| Point::new(i64::from(pos.column), i64::from(pos.row)))
+        .collect::<Vec<_>>();
+    u32::try_from(polygon::interior_points(&vertices)).ok()
 }
 
 #[cfg(test)]
@@ -432,21 +288,19 @@ L--J.L7...LJS7F-7L7.
     }
 
     #[test]
-    fn test_part_two_single_point() {
-        let input = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-        let (map, start) = input_to_map(input);
-        let path = find_path(&map, &start).unwrap();
-        let map_path = Path::new(path, map);
-        let p = Position { row: 6, column: 2 };
-        let result = map_path.point_contained(&p);
-        assert_eq!(result, true);
+    fn test_part_two_c() {
+        // The start pipe's neighbours to the east and west don't connect back to it
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        assert_eq!(part_one(input), Some(80));
+        assert_eq!(part_two(input), Some(10));
     }
 }
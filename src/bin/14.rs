@@ -1,16 +1,21 @@
 advent_of_code::solution!(14);
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
+use advent_of_code::cycle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Rock {
     Round,
     Cube,
 }
 
+// An ordered map of the rocks, so whole maps can be hashed and compared while looking for a cycle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct RockMap {
     rows: usize,
     cols: usize,
-    rocks: HashMap<(usize, usize), Rock>,
+    rocks: BTreeMap<(usize, usize), Rock>,
 }
 
 impl RockMap {
@@ -18,7 +23,7 @@ impl RockMap {
         RockMap {
             rows,
             cols,
-            rocks: HashMap::new(),
+            rocks: BTreeMap::new(),
         }
     }
 
@@ -172,29 +177,15 @@ impl RockMap {
             println!();
         }
     }
-    fn map_hash(&self) -> String {
-        let mut map = String::new();
-        for row in 0..=self.rows {
-            for col in 0..=self.cols {
-                if let Some(rock) = self.rocks.get(&(row, col)) {
-                    match rock {
-                        Rock::Cube => map.push('#'),
-                        Rock::Round => map.push('O'),
-                    }
-                } else {
-                    map.push('.');
-                }
-            }
-            map.push('\n');
-        }
-        map
-    }
 }
 
 fn rock_map_from_input(input: &str) -> RockMap {
     let mut rock_map = RockMap::new(0, 0);
     for (row, line) in input.lines().enumerate() {
+        // size the map by its lines, rows and columns at the edges may have no rocks
+        rock_map.rows = row;
         for (col, c) in line.chars().enumerate() {
+            rock_map.cols = rock_map.cols.max(col);
             match c {
                 'O' => {
                     rock_map.add_rock(row, col, Rock::Round);
@@ -216,36 +207,16 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut rock_map = rock_map_from_input(input);
-    let mut seen_maps = Vec::new();
-    let mut loads = Vec::new();
-    // let num_cycles = 1_000_000_000;
-    let num_cycles = 1_000_000_000;
-
-    // Run until we find a repeating pattern
-    for _i in 0..num_cycles {
+    let spin = |rock_map: &RockMap| {
+        let mut rock_map = rock_map.clone();
         rock_map.cycle();
-        let h = rock_map.map_hash();
-        let index_seen_before = seen_maps
-            .iter()
-            .enumerate()
-            .find(|(_, m)| *m == &h)
-            .map(|(i, _)| i);
-        match index_seen_before {
-            Some(index) => {
-                // We've seen this map before so we can calculate the load for the 1 billionth cycle
-                let cycle_length = seen_maps.len() - index;
-                let cycle = (num_cycles - index - 1) % cycle_length;
-                let load = loads[index + cycle];
-                return Some(load);
-            }
-            None => {
-                seen_maps.push(h);
-                loads.push(rock_map.load());
-            }
-        }
-    }
-    None
+        rock_map
+    };
+
+    // the rocks settle into a loop long before the billionth cycle
+    let rock_map = cycle::state_at(rock_map_from_input(input), spin, 1_000_000_000);
+
+    Some(rock_map.load())
 }
 
 #[cfg(test)]
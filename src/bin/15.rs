@@ -8,7 +8,7 @@ enum Operation<'a> {
     TakeLens(&'a str),
 }
 
-fn word_to_operation(word: &str) -> Operation {
+fn word_to_operation(word: &str) -> Operation<'_> {
     let mut last_index_of_id = 0;
     let mut focal_power = 0;
     let mut operator_char = ' ';
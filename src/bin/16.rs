@@ -292,15 +292,14 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 pub fn part_two(input: &str) -> Option<usize> {
     let mut mirror_map = parse_input(input);
-    let result = permute_start_beams(&mirror_map)
+    permute_start_beams(&mirror_map)
         .into_iter()
         .map(|start_beam| {
             mirror_map.initialise_beam(start_beam);
             mirror_map.move_all_beams();
             mirror_map.num_lit_points()
         })
-        .max();
-    Some(result?)
+        .max()
 }
 
 #[cfg(test)]
@@ -1,10 +1,8 @@
-use petgraph::{algo::dijkstra, graph::Node, graph::NodeIndex};
-use std::collections::{HashMap, HashSet};
-
 advent_of_code::solution!(17);
 
-use petgraph::graph::DiGraph;
+use advent_of_code::{search, Direction4, Grid};
 
+/// The orientation of the last move, the next move has to turn.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Orientation {
     Horizontal,
@@ -18,323 +16,84 @@ struct Location {
     col: usize,
 }
 
-fn graph_from_input_part_1(
-    input: &str,
-) -> (DiGraph<Location, u32>, NodeIndex, NodeIndex, NodeIndex) {
-    let mut graph = DiGraph::new();
-    let mut node_map = HashMap::new();
-    let mut rows = 0;
-    let mut cols = 0;
-    let mut locations = HashMap::new();
-    let mut start: Option<NodeIndex> = None;
-    let mut end_1: Option<NodeIndex> = None;
-    let mut end_2: Option<NodeIndex> = None;
-    for (row, line) in input.lines().enumerate() {
-        rows = row + 1;
-        for (col, c) in line.chars().enumerate() {
-            if col >= cols {
-                cols = col + 1;
-            }
-            let loss = c.to_digit(10).unwrap_or(0);
-            locations.insert((row, col), loss);
-            let location = Location {
-                orientation: Orientation::Horizontal,
-                row,
-                col,
-            };
-
-            let index = graph.add_node(location);
-            if (row, col) == (0, 0) {
-                start = Some(index);
-            }
-            if (row, col) == (rows - 1, cols - 1) {
-                end_1 = Some(index);
-            }
-            node_map.insert(location, index);
-
-            let location = Location {
-                orientation: Orientation::Vertical,
-                row,
-                col,
-            };
-            let index = graph.add_node(location);
-            if (row, col) == (rows - 1, cols - 1) {
-                end_2 = Some(index);
-            }
-            node_map.insert(location, index);
-        }
-    }
-    // Now loop over all locations and add edges
-    for row in 0..rows {
-        for col in 0..cols {
-            let min_row = row.saturating_sub(3);
-            let max_row = rows.min(row + 4);
-            let min_col = col.saturating_sub(3);
-            let max_col = cols.min(col + 4);
-            // Add horizontal edges to vertical nodes
-            let mut loss = 0;
-            let from = Location {
-                orientation: Orientation::Horizontal,
-                row,
-                col,
-            };
-            let from_index = node_map.get(&from).unwrap();
-
-            for r in (min_row..row).rev() {
-                loss += locations.get(&(r, col)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Vertical,
-                    row: r,
-                    col,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-            loss = 0;
-            for r in row..max_row {
-                if r == row {
-                    continue;
-                }
-                loss += locations.get(&(r, col)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Vertical,
-                    row: r,
-                    col,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-
-            loss = 0;
-            let from = Location {
-                orientation: Orientation::Vertical,
-                row,
-                col,
-            };
-            let from_index = node_map.get(&from).unwrap();
-            for c in (min_col..col).rev() {
-                loss += locations.get(&(row, c)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Horizontal,
-                    row,
-                    col: c,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-
-            loss = 0;
-            for c in col..max_col {
-                if c == col {
-                    continue;
-                }
-                loss += locations.get(&(row, c)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Horizontal,
-                    row,
-                    col: c,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-        }
-    }
-
-    (graph, start.unwrap(), end_1.unwrap(), end_2.unwrap())
+fn parse(input: &str) -> Option<Grid<u32>> {
+    Grid::parse(input, |c| c.to_digit(10).unwrap_or(0)).ok()
 }
 
-// Minimum move of 4, max of 10
-fn graph_from_input_part_2(
-    input: &str,
-) -> (DiGraph<Location, u32>, NodeIndex, NodeIndex, NodeIndex) {
-    let mut graph = DiGraph::new();
-    let mut node_map = HashMap::new();
-    let mut rows = 0;
-    let mut cols = 0;
-    let mut locations = HashMap::new();
-    let mut start: Option<NodeIndex> = None;
-    let mut end_1: Option<NodeIndex> = None;
-    let mut end_2: Option<NodeIndex> = None;
-    for (row, line) in input.lines().enumerate() {
-        rows = row + 1;
-        for (col, c) in line.chars().enumerate() {
-            if col >= cols {
-                cols = col + 1;
-            }
-            let loss = c.to_digit(10).unwrap_or(0);
-            locations.insert((row, col), loss);
-            let location = Location {
-                orientation: Orientation::Horizontal,
-                row,
-                col,
+/// The locations reachable by turning and moving `min` to `max` blocks straight ahead,
+/// with the heat lost along the way.
+fn moves(grid: &Grid<u32>, from: Location, min: usize, max: usize) -> Vec<(Location, u32)> {
+    let (directions, orientation) = match from.orientation {
+        Orientation::Horizontal => ([Direction4::Up, Direction4::Down], Orientation::Vertical),
+        Orientation::Vertical => (
+            [Direction4::Left, Direction4::Right],
+            Orientation::Horizontal,
+        ),
+    };
+
+    let mut moves = Vec::new();
+
+    for direction in directions {
+        let mut position = (from.col, from.row);
+        let mut loss = 0;
+
+        for blocks in 1..=max {
+            let Some(next) = grid.offset(position, direction.offset()) else {
+                break;
             };
-
-            let index = graph.add_node(location);
-            if (row, col) == (0, 0) {
-                start = Some(index);
-            }
-            if (row, col) == (rows - 1, cols - 1) {
-                end_1 = Some(index);
+            position = next;
+            loss += grid[position];
+
+            if blocks >= min {
+                let (col, row) = position;
+                moves.push((
+                    Location {
+                        orientation,
+                        row,
+                        col,
+                    },
+                    loss,
+                ));
             }
-            node_map.insert(location, index);
-
-            let location = Location {
-                orientation: Orientation::Vertical,
-                row,
-                col,
-            };
-            let index = graph.add_node(location);
-            if (row, col) == (rows - 1, cols - 1) {
-                end_2 = Some(index);
-            }
-            node_map.insert(location, index);
         }
     }
-    // Now loop over all locations and add edges
-    for row in 0..rows {
-        for col in 0..cols {
-            if row == 4 && col == 8 {
-                println!("here");
-            }
-            let min_row = row.saturating_sub(10);
-            let max_row = row.saturating_sub(3);
-            // Add horizontal edges to vertical nodes
-            // Even though we can't stop at the first 4 places, we still need to count the loss
-            let mut loss = (max_row..row)
-                .map(|r| locations.get(&(r, col)).unwrap())
-                .sum();
-            let from = Location {
-                orientation: Orientation::Horizontal,
-                row,
-                col,
-            };
-            let from_index = node_map.get(&from).unwrap();
 
-            for r in (min_row..max_row).rev() {
-                loss += locations.get(&(r, col)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Vertical,
-                    row: r,
-                    col,
-                };
-                let to_index = node_map.get(&to).unwrap();
+    moves
+}
 
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-            let min_row = rows.min(row + 4);
-            let max_row = rows.min(row + 11);
-            loss = (row..min_row)
-                .filter(|r| *r != row)
-                .map(|r| locations.get(&(r, col)).unwrap())
-                .sum();
-            for r in min_row..max_row {
-                if r == row {
-                    continue;
-                }
-                loss += locations.get(&(r, col)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Vertical,
-                    row: r,
-                    col,
-                };
-                let to_index = node_map.get(&to).unwrap();
+/// The least heat lost on the way from `start` to the bottom right block.
+fn least_heat_loss(grid: &Grid<u32>, start: Location, min: usize, max: usize) -> Option<u32> {
+    let end = (grid.width() - 1, grid.height() - 1);
+    let path = search::dijkstra(
+        start,
+        |&location| moves(grid, location, min, max),
+        |location| (location.col, location.row) == end,
+    )?;
 
-                graph.add_edge(*from_index, *to_index, loss);
-            }
+    Some(path.cost)
+}
 
-            let min_col = col.saturating_sub(10);
-            let max_col = col.saturating_sub(3);
-            loss = (max_col..col)
-                .map(|c| locations.get(&(row, c)).unwrap())
-                .sum();
-            let from = Location {
-                orientation: Orientation::Vertical,
-                row,
-                col,
+/// The least heat lost from the top left to the bottom right block, starting in either direction.
+fn least_heat_loss_from_start(grid: &Grid<u32>, min: usize, max: usize) -> Option<u32> {
+    [Orientation::Horizontal, Orientation::Vertical]
+        .into_iter()
+        .filter_map(|orientation| {
+            let start = Location {
+                orientation,
+                row: 0,
+                col: 0,
             };
-            let from_index = node_map.get(&from).unwrap();
-            for c in (min_col..max_col).rev() {
-                loss += locations.get(&(row, c)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Horizontal,
-                    row,
-                    col: c,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-
-            let min_col = cols.min(col + 4);
-            let max_col = cols.min(col + 11);
-            loss = (col..min_col)
-                .filter(|c| *c != col)
-                .map(|c| locations.get(&(row, c)).unwrap())
-                .sum();
-            for c in min_col..max_col {
-                if c == col {
-                    continue;
-                }
-                loss += locations.get(&(row, c)).unwrap();
-                let to = Location {
-                    orientation: Orientation::Horizontal,
-                    row,
-                    col: c,
-                };
-                let to_index = node_map.get(&to).unwrap();
-
-                graph.add_edge(*from_index, *to_index, loss);
-            }
-        }
-    }
-
-    (graph, start.unwrap(), end_1.unwrap(), end_2.unwrap())
+            least_heat_loss(grid, start, min, max)
+        })
+        .min()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (graph, start, end_1, end_2) = graph_from_input_part_1(input);
-    let node_map_1 = dijkstra(&graph, start, Some(end_1), |e| *e.weight());
-    let node_map_2 = dijkstra(&graph, start, Some(end_2), |e| *e.weight());
-    let dist_1 = node_map_1.get(&end_1)?;
-    let dist_2 = node_map_2.get(&end_2)?;
-
-    Some(*dist_1.min(dist_2))
+    least_heat_loss_from_start(&parse(input)?, 1, 3)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (graph, start_1, end_1, end_2) = graph_from_input_part_2(input);
-    let start_2 = find_node_index(
-        &graph,
-        Location {
-            orientation: Orientation::Vertical,
-            row: 0,
-            col: 0,
-        },
-    )?;
-    let node_map_1_1 = dijkstra(&graph, start_1, Some(end_1), |e| *e.weight());
-    let node_map_2_1 = dijkstra(&graph, start_1, Some(end_2), |e| *e.weight());
-    let node_map_1_2 = dijkstra(&graph, start_2, Some(end_1), |e| *e.weight());
-    let node_map_2_2 = dijkstra(&graph, start_2, Some(end_2), |e| *e.weight());
-    let dist_1_1 = node_map_1_1.get(&end_1)?;
-    let dist_2_1 = node_map_2_1.get(&end_2)?;
-    let dist_1_2 = node_map_1_2.get(&end_1)?;
-    let dist_2_2 = node_map_2_2.get(&end_2)?;
-    let best = dist_1_1.min(dist_2_1).min(dist_1_2).min(dist_2_2);
-
-    Some(*best)
-}
-
-fn find_node_index(graph: &DiGraph<Location, u32>, location: Location) -> Option<NodeIndex> {
-    for node in graph.node_indices() {
-        if graph[node] == location {
-            return Some(node);
-        }
-    }
-    None
+    least_heat_loss_from_start(&parse(input)?, 4, 10)
 }
 
 #[cfg(test)]
@@ -364,20 +123,13 @@ mod tests {
         #[case] orientation: Orientation,
         #[case] expected: u32,
     ) {
-        let (graph, _start, end_1, end_2) =
-            graph_from_input_part_1(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
         let location = Location {
             orientation,
             row: row as usize,
             col: col as usize,
         };
-        let node_index = find_node_index(&graph, location).unwrap();
-        let node_map = dijkstra(&graph, node_index, Some(end_1), |e| *e.weight());
-        let dist_1 = node_map.get(&end_1).unwrap();
-        let node_map = dijkstra(&graph, node_index, Some(end_2), |e| *e.weight());
-        let dist_2 = node_map.get(&end_2).unwrap();
-        let dist = dist_1.min(dist_2);
-        assert_eq!(*dist, expected);
+        assert_eq!(least_heat_loss(&grid, location, 1, 3), Some(expected));
     }
 
     #[rstest::rstest]
@@ -391,19 +143,12 @@ mod tests {
         #[case] orientation: Orientation,
         #[case] expected: u32,
     ) {
-        let (graph, _start, end_1, end_2) =
-            graph_from_input_part_2(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
         let location = Location {
             orientation,
             row: row as usize,
             col: col as usize,
         };
-        let node_index = find_node_index(&graph, location).unwrap();
-        let node_map = dijkstra(&graph, node_index, Some(end_1), |e| *e.weight());
-        let dist_1 = node_map.get(&end_1).unwrap();
-        let node_map = dijkstra(&graph, node_index, Some(end_2), |e| *e.weight());
-        let dist_2 = node_map.get(&end_2).unwrap();
-        let dist = dist_1.min(dist_2);
-        assert_eq!(*dist, expected);
+        assert_eq!(least_heat_loss(&grid, location, 4, 10), Some(expected));
     }
 }
@@ -1,118 +1,88 @@
 advent_of_code::solution!(18);
 
-use std::collections::HashMap;
+use advent_of_code::{parse_lines, polygon, Direction4, Point, PuzzleError};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Instruction<'a> {
+    direction: Direction4,
+    distance: i64,
+    colour: &'a str,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Line {
-    x1: i32,
-    y1: i32,
-    x2: i32,
-    y2: i32,
-    direction: Direction,
-}
+impl Instruction<'_> {
+    // The actual instruction is hidden in the colour: the first five hex digits are the distance
+    // and the last one is the direction
+    fn decode(&self) -> Result<(Direction4, i64), PuzzleError> {
+        let invalid = || PuzzleError::new(format!("invalid colour \"{}\"", self.colour));
+
+        let hex = self
+            .colour
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(invalid)?;
+        let (distance, direction) = hex.split_at(5);
 
-fn input_to_vertical_lines_part_1(input: &str) -> Vec<Line> {
-    let mut lines = Vec::new();
-    let (mut x, mut y) = (0, 0);
-
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let direction_char = parts.next().unwrap();
-        let count = parts.next().unwrap().parse::<u32>().unwrap();
-        // let color = u32::from_str_radix(&color[1..], 16).unwrap();
-        let (dx, dy, direction) = match direction_char {
-            "U" => (0, -1, Some(Direction::Up)),
-            "D" => (0, 1, Some(Direction::Down)),
-            "L" => (-1, 0, None),
-            "R" => (1, 0, None),
-            _ => panic!("Invalid direction"),
+        let distance = i64::from_str_radix(distance, 16).map_err(|e| invalid().with_source(e))?;
+        let direction = match direction {
+            "0" => Direction4::Right,
+            "1" => Direction4::Down,
+            "2" => Direction4::Left,
+            "3" => Direction4::Up,
+            _ => return Err(invalid()),
         };
-        let x1 = x;
-        let y1 = y;
-        let x2 = x + dx * count as i32;
-        let y2 = y + dy * count as i32;
-        if let Some(direction) = direction {
-            // Only save vertical lines
-            lines.push(Line {
-                x1,
-                y1,
-                x2,
-                y2,
-                direction,
-            });
-        }
-        // Update for the next line
-        x = x2;
-        y = y2;
+        Ok((direction, distance))
     }
-    lines
 }
 
-// Use ray marching to find the area of the hole
-// By looping row by row and considering vertical lines
-fn lines_to_area(lines: &[Line]) -> u32 {
-    // Only look at starts of line as there will always be a corresponding other vertical line
-    // That starts at the end of the line (even if it's not directly after this line)
-    let mut rows_of_interest = lines.iter().map(|line| line.y1).collect::<Vec<_>>();
-    rows_of_interest.sort_unstable();
-    rows_of_interest.dedup();
-
-    let min_row = rows_of_interest[0];
-    let max_row = rows_of_interest[rows_of_interest.len() - 1];
-
-    let mut total_area = 0;
-    for row in min_row..=max_row {
-        let lines_of_interest = lines
-            .iter()
-            .filter(|line| {
-                // Only consider lines that are in the range of the current rows
-                match line.direction {
-                    Direction::Up => row <= line.y1 && row >= line.y2,
-                    Direction::Down => row >= line.y1 && row <= line.y2,
-                }
-            })
-            .collect::<Vec<_>>();
-
-        let mut cols_of_interest = lines_of_interest
-            .iter()
-            .flat_map(|line| vec![line.x1, line.x2])
-            .collect::<Vec<_>>();
-        cols_of_interest.sort_unstable();
-        cols_of_interest.dedup();
-        total_area += cols_of_interest.windows(2).fold(0, |area, col| {
-            let (col1, col2) = (col[0], col[1]);
-            let mut inside = false;
-            for line in lines_of_interest.iter() {
-                if line.x1 <= col1 {
-                    if line.direction == Direction::Up && !inside {
-                        inside = true;
-                    } else if line.direction == Direction::Down && inside {
-                        inside = false;
-                    }
-                }
-            }
-            if inside {
-                return area + (col2 - col1) as u32;
-            }
-            0
-        }) + 1;
-    }
-    total_area
+// Parse a line like "R 6 (#70c710)"
+fn parse_instruction(line: &str) -> Result<Instruction<'_>, PuzzleError> {
+    let mut parts = line.split_whitespace();
+    let (Some(direction), Some(distance), Some(colour), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(PuzzleError::new(
+            "expected a direction, a distance and a colour",
+        ));
+    };
+
+    let colour = colour
+        .strip_prefix('(')
+        .and_then(|colour| colour.strip_suffix(')'))
+        .ok_or_else(|| PuzzleError::new(format!("invalid colour \"{colour}\"")))?;
+
+    Ok(Instruction {
+        direction: direction.parse()?,
+        distance: distance.parse()?,
+        colour,
+    })
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let vertical_lines = input_to_vertical_lines_part_1(input);
-    Some(lines_to_area(&vertical_lines))
+// Dig the trench along the given steps and return the size of the lagoon, trench included.
+// Every cube of the trench is a lattice point on the boundary of the polygon through the
+// corners of the trench, so the lagoon is every lattice point inside or on that polygon.
+fn lagoon_size(steps: impl IntoIterator<Item = (Direction4, i64)>) -> Result<u64, PuzzleError> {
+    let mut position = Point::new(0, 0);
+    let trench = steps
+        .into_iter()
+        .map(|(direction, distance)| {
+            position = position.step_n(direction, distance);
+            position
+        })
+        .collect::<Vec<_>>();
+
+    u64::try_from(polygon::lattice_points(&trench)).map_err(PuzzleError::wrap)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_one(input: &str) -> Result<u64, PuzzleError> {
+    let steps = parse_lines(input, |line| {
+        parse_instruction(line).map(|instruction| (instruction.direction, instruction.distance))
+    })?;
+    lagoon_size(steps)
+}
+
+pub fn part_two(input: &str) -> Result<u64, PuzzleError> {
+    let steps = parse_lines(input, |line| parse_instruction(line)?.decode())?;
+    lagoon_size(steps)
 }
 
 #[cfg(test)]
@@ -122,12 +92,24 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(62));
+        assert_eq!(result.unwrap(), 62);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result.unwrap(), 952408144115);
+    }
+
+    #[test]
+    fn test_decode() {
+        let instruction = parse_instruction("R 6 (#70c710)").unwrap();
+        assert_eq!(instruction.decode().unwrap(), (Direction4::Right, 461937));
+
+        let instruction = parse_instruction("U 2 (#7a21e3)").unwrap();
+        assert_eq!(instruction.decode().unwrap(), (Direction4::Up, 500254));
+
+        let error = part_two("R 6 (#70c710)\nL 6 (#70c714)").unwrap_err();
+        assert_eq!(error.line(), Some(2));
     }
 }
@@ -0,0 +1,225 @@
+/// Module that detects cycles in sequences of states, e.g. of a simulation that has to run for a
+/// billion steps but starts repeating itself much earlier.
+///
+/// A sequence starts with a state and applies `step` to get the next one. Once a state repeats,
+/// so does everything after it: the sequence is described by the number of steps before it enters
+/// its cycle and the length of the cycle. Every state can then be found among the first few.
+///
+/// [`find`] and [`state_at`] remember every state in a hash map. [`floyd`] and [`brent`] only keep
+/// two states at a time for states that are expensive to store, or that cannot be hashed, at the
+/// cost of running `step` more often.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence of states repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the sequence enters its cycle.
+    pub prefix: usize,
+    /// The number of steps after which the states of the cycle repeat.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state after `step` steps, i.e. a step within the
+    /// prefix or the first run through the cycle. Use it to look up values computed for the
+    /// states that were seen while detecting the cycle.
+    #[must_use]
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.prefix {
+            return step;
+        }
+
+        self.prefix + (step - self.prefix) % self.period
+    }
+}
+
+/// Applies `step` until a state repeats. Returns the cycle and every state before the repetition,
+/// the state after `i` steps at index `i`.
+///
+/// Never returns for sequences that do not repeat.
+pub fn find<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start];
+
+    loop {
+        let next = step(states.last().expect("the start is always seen"));
+
+        if let Some(&prefix) = seen.get(&next) {
+            let period = states.len() - prefix;
+            return (Cycle { prefix, period }, states);
+        }
+
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+}
+
+/// The state after applying `step` `n` times to `start`. Only runs `step` until the states
+/// repeat, so `n` can be far larger than what could be simulated.
+pub fn state_at<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start];
+
+    while states.len() <= n {
+        let next = step(states.last().expect("the start is always seen"));
+
+        if let Some(&prefix) = seen.get(&next) {
+            let cycle = Cycle {
+                prefix,
+                period: states.len() - prefix,
+            };
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    states.swap_remove(n)
+}
+
+/// Finds the cycle with Floyd's tortoise and hare algorithm.
+///
+/// Never returns for sequences that do not repeat.
+pub fn floyd<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // the hare is a multiple of the period ahead, so both meet where the cycle starts
+    let mut prefix = 0;
+    tortoise = start;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Finds the cycle with Brent's algorithm, which usually needs fewer steps than [`floyd`].
+///
+/// Never returns for sequences that do not repeat.
+pub fn brent<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // with the hare a period ahead, both meet where the cycle starts
+    let mut prefix = 0;
+    tortoise = start.clone();
+    hare = start;
+
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, find, floyd, state_at, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, ...
+    fn step(n: &u32) -> u32 {
+        if *n < 5 {
+            n + 1
+        } else {
+            2
+        }
+    }
+
+    fn lcg(n: &u64) -> u64 {
+        (n * n + 1) % 255
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            prefix: 2,
+            period: 4,
+        };
+
+        let (cycle, states) = find(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+
+        let (cycle, states) = find(3, lcg);
+        assert_eq!(states.len(), cycle.prefix + cycle.period);
+        assert_eq!(floyd(3, lcg), cycle);
+        assert_eq!(brent(3, lcg), cycle);
+    }
+
+    #[test]
+    fn finds_cycles_without_prefix() {
+        let cycle = Cycle {
+            prefix: 0,
+            period: 4,
+        };
+
+        assert_eq!(find(2, step).0, cycle);
+        assert_eq!(floyd(2, step), cycle);
+        assert_eq!(brent(2, step), cycle);
+        assert_eq!(
+            brent(7, |n| *n),
+            Cycle {
+                prefix: 0,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    fn skips_to_steps() {
+        let (cycle, _) = find(0, step);
+        assert_eq!(cycle.equivalent_step(1), 1);
+        assert_eq!(cycle.equivalent_step(6), 2);
+        assert_eq!(cycle.equivalent_step(1_000_000_001), 5);
+
+        assert_eq!(state_at(0, step, 0), 0);
+        assert_eq!(state_at(0, step, 4), 4);
+        assert_eq!(state_at(0, step, 1_000_000_000), 4);
+
+        let mut state = 3;
+        for _ in 0..1000 {
+            state = lcg(&state);
+        }
+        assert_eq!(state_at(3, lcg, 1000), state);
+    }
+}
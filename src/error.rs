@@ -0,0 +1,279 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+/// An error in the puzzle input.
+///
+/// Knows the line and column it occurred at, so that parse failures point at the offending part
+/// of the input. Errors created while parsing a single line can be located with [`parse_lines`]
+/// or [`PuzzleError::at_line`] afterwards.
+#[derive(Debug)]
+pub struct PuzzleError {
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    text: Option<String>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+    /// The error this one was created from by [`PuzzleError::wrap`], its message is the message
+    /// of this error.
+    wrapped: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl PuzzleError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+            text: None,
+            source: None,
+            wrapped: None,
+        }
+    }
+
+    /// Creates an error located at the byte `offset` of `input`.
+    #[must_use]
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = input[..line_start].matches('\n').count() + 1;
+        let text = input[line_start..].lines().next().unwrap_or_default();
+        let column = input[line_start..offset].chars().count() + 1;
+
+        Self::new(message).at_line(line, text).at_column(column)
+    }
+
+    /// Wraps another error, e.g. a failure to parse a number. The error keeps its message, its
+    /// causes become the source of the new error.
+    #[must_use]
+    pub fn wrap(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        let error = error.into();
+
+        match error.downcast::<Self>() {
+            Ok(error) => *error,
+            Err(error) => Self {
+                message: error.to_string(),
+                wrapped: Some(error),
+                ..Self::new("")
+            },
+        }
+    }
+
+    /// Sets the line the error occurred at, starting at 1, and the text of that line.
+    #[must_use]
+    pub fn at_line(mut self, line: usize, text: &str) -> Self {
+        self.line = Some(line);
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Sets the column within the line the error occurred at, starting at 1.
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Moves the line of an error located within a part of the input `lines` lines down.
+    #[must_use]
+    pub(crate) fn offset_lines(mut self, lines: usize) -> Self {
+        self.line = self.line.map(|line| line + lines);
+        self
+    }
+
+    /// Attaches the error that caused this one.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error this one was created from by [`PuzzleError::wrap`], e.g. to downcast it.
+    #[must_use]
+    pub fn wrapped(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.wrapped.as_deref()
+    }
+
+    #[must_use]
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    #[must_use]
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            (None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None) => {}
+        }
+
+        f.write_str(&self.message)?;
+
+        if let Some(text) = &self.text {
+            write!(f, "\n    {text}")?;
+
+            if let Some(column) = self.column {
+                write!(f, "\n    {}^", " ".repeat(column.saturating_sub(1)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match (&self.source, &self.wrapped) {
+            (Some(source), _) => Some(&**source),
+            (None, Some(wrapped)) => wrapped.source(),
+            (None, None) => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for PuzzleError {
+    fn from(error: ParseIntError) -> Self {
+        Self::wrap(error)
+    }
+}
+
+/// Parses every line of `input` with `parse`, locating errors at the line they occurred in.
+/// Columns of [`PuzzleError`]s returned by `parse` are kept.
+pub fn parse_lines<'a, T, E>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>, PuzzleError>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| PuzzleError::wrap(e).at_line(i + 1, line)))
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::num::ParseIntError;
+
+    use super::{parse_lines, PuzzleError};
+
+    #[derive(Debug)]
+    struct SettingError(ParseIntError);
+
+    impl Display for SettingError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid setting")
+        }
+    }
+
+    impl Error for SettingError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn locates_offsets() {
+        let error = PuzzleError::at_offset("abc\ndefg\n", 6, "unexpected 'f'");
+
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: unexpected 'f'\n    defg\n      ^"
+        );
+    }
+
+    #[test]
+    fn locates_line_errors() {
+        let error = parse_lines("1 2\n3 x\n", |line| {
+            line.split(' ')
+                .map(|n| n.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .unwrap_err();
+
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), None);
+        assert_eq!(error.message(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn keeps_columns() {
+        let error = parse_lines("ab\nax\n", |line| match line.find('x') {
+            Some(i) => Err(PuzzleError::new("unknown 'x'").at_column(i + 1)),
+            None => Ok(line.len()),
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: unknown 'x'\n    ax\n     ^"
+        );
+    }
+
+    #[test]
+    fn keeps_error_chains() {
+        let error = parse_lines("a=1\nb=x\n", |line| {
+            let (_, value) = line.split_once('=').unwrap();
+            value.parse::<u32>().map_err(SettingError)
+        })
+        .unwrap_err();
+
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.message(), "invalid setting");
+        assert!(error
+            .wrapped()
+            .is_some_and(|e| e.downcast_ref::<SettingError>().is_some()));
+
+        let cause = error.source().unwrap();
+        assert!(cause.downcast_ref::<ParseIntError>().is_some());
+        assert_eq!(cause.to_string(), "invalid digit found in string");
+        assert!(cause.source().is_none());
+    }
+
+    #[test]
+    fn allows_column_zero() {
+        let error = PuzzleError::new("unexpected end")
+            .at_line(1, "abc")
+            .at_column(0);
+
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 0: unexpected end\n    abc\n    ^"
+        );
+    }
+
+    #[test]
+    fn locates_offsets_within_characters() {
+        // byte 2 is within the two bytes of 'í'
+        let error = PuzzleError::at_offset("día 3", 2, "unexpected 'í'");
+
+        assert_eq!(error.column(), Some(2));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 2: unexpected 'í'\n    día 3\n     ^"
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::PuzzleError;
+
+/// A point on a 2D plane.
+///
+/// Like [`Grid`](crate::Grid), points use screen coordinates: `x` grows to the right and `y`
+/// grows downwards, so [`Direction4::Up`] decreases `y`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T> + Mul<Output = T>> Point<T> {
+    /// Moves the point one step into `direction`.
+    #[must_use]
+    pub fn step(self, direction: impl Into<Direction8>) -> Self {
+        self.step_n(direction, T::from(1))
+    }
+
+    /// Moves the point `n` steps into `direction`.
+    #[must_use]
+    pub fn step_n(self, direction: impl Into<Direction8>, n: T) -> Self {
+        let (dx, dy) = direction.into().delta();
+        Self::new(self.x + T::from(dx) * n, self.y + T::from(dy) * n)
+    }
+}
+
+/// Stepping for the unsigned coordinates of [`Grid`](crate::Grid) positions, which cannot use
+/// [`Point::step`] as they may not become negative.
+impl Point<usize> {
+    /// Moves the point one step into `direction`. Returns [`None`] if a coordinate would become
+    /// negative.
+    #[must_use]
+    pub fn checked_step(self, direction: impl Into<Direction8>) -> Option<Self> {
+        self.checked_step_n(direction, 1)
+    }
+
+    /// Moves the point `n` steps into `direction`. Returns [`None`] if a coordinate would become
+    /// negative or overflow.
+    #[must_use]
+    pub fn checked_step_n(self, direction: impl Into<Direction8>, n: usize) -> Option<Self> {
+        let (dx, dy) = direction.into().delta();
+        Some(Self::new(move_by(self.x, dx, n)?, move_by(self.y, dy, n)?))
+    }
+
+    /// Moves the point one step into `direction`. Returns [`None`] if the new point lies outside
+    /// of a `width` x `height` area starting at the origin.
+    #[must_use]
+    pub fn step_within(
+        self,
+        direction: impl Into<Direction8>,
+        width: usize,
+        height: usize,
+    ) -> Option<Self> {
+        self.checked_step(direction)
+            .filter(|point| point.x < width && point.y < height)
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The distance to `other` when moving orthogonally only.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance to `other` when moving diagonally is allowed.
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+/// Moves an unsigned `coordinate` `n` times by `delta`, which is `-1`, `0` or `1`.
+fn move_by(coordinate: usize, delta: i8, n: usize) -> Option<usize> {
+    match delta.cmp(&0) {
+        Ordering::Less => coordinate.checked_sub(n),
+        Ordering::Equal => Some(coordinate),
+        Ordering::Greater => coordinate.checked_add(n),
+    }
+}
+
+/// The difference of `a` and `b`, without underflowing for unsigned types.
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The direction after turning 90 degrees counter-clockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The direction after turning 90 degrees clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    #[must_use]
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    #[must_use]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    /// The `(dx, dy)` offset of one step into this direction,
+    /// e.g. for [`Grid::offset`](crate::Grid::offset).
+    #[must_use]
+    pub const fn offset(self) -> (isize, isize) {
+        Direction8::from_direction4(self).offset()
+    }
+}
+
+impl TryFrom<char> for Direction4 {
+    type Error = PuzzleError;
+
+    /// Parses `U`, `R`, `D` and `L` as well as `N`, `E`, `S` and `W`, ignoring case.
+    fn try_from(c: char) -> Result<Self, PuzzleError> {
+        match c.to_ascii_uppercase() {
+            'U' | 'N' => Ok(Self::Up),
+            'R' | 'E' => Ok(Self::Right),
+            'D' | 'S' => Ok(Self::Down),
+            'L' | 'W' => Ok(Self::Left),
+            _ => Err(PuzzleError::new(format!("unknown direction '{c}'"))),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = PuzzleError;
+
+    /// Parses a single direction character, see [`Direction4::try_from`].
+    fn from_str(s: &str) -> Result<Self, PuzzleError> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(PuzzleError::new(format!("unknown direction \"{s}\""))),
+        }
+    }
+}
+
+/// One of the eight directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The direction after turning 45 degrees counter-clockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The direction after turning 45 degrees clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The `(dx, dy)` offset of one step into this direction,
+    /// e.g. for [`Grid::offset`](crate::Grid::offset).
+    #[must_use]
+    pub const fn offset(self) -> (isize, isize) {
+        let (dx, dy) = self.delta();
+        (dx as isize, dy as isize)
+    }
+
+    const fn delta(self) -> (i8, i8) {
+        match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        }
+    }
+
+    const fn from_direction4(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::from_direction4(direction)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction4, Direction8, Point};
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        assert_eq!(Direction4::Right.reverse(), Direction4::Left);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert_eq!(Direction8::from(Direction4::Down), Direction8::Down);
+    }
+
+    #[test]
+    fn parses_directions() {
+        let parsed: Vec<Direction4> = "URDLNESWu".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(&parsed[..4], &Direction4::ALL);
+        assert_eq!(&parsed[4..8], &Direction4::ALL);
+        assert_eq!(parsed[8], Direction4::Up);
+
+        assert_eq!("R".parse::<Direction4>().unwrap(), Direction4::Right);
+        assert!("X".parse::<Direction4>().is_err());
+        assert!("UR".parse::<Direction4>().is_err());
+    }
+
+    #[test]
+    fn steps_points() {
+        let point = Point::new(3i64, 4);
+        assert_eq!(point.step(Direction4::Up), Point::new(3, 3));
+        assert_eq!(point.step(Direction8::DownLeft), Point::new(2, 5));
+        assert_eq!(point.step_n(Direction4::Right, 10), Point::new(13, 4));
+
+        let origin = Point::new(0usize, 0);
+        assert_eq!(origin.checked_step(Direction4::Left), None);
+        assert_eq!(
+            origin.checked_step(Direction8::DownRight),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(
+            Point::new(5usize, 2).checked_step_n(Direction8::UpLeft, 2),
+            Some(Point::new(3, 0))
+        );
+        assert_eq!(origin.checked_step_n(Direction4::Up, 0), Some(origin));
+        assert_eq!(
+            Point::new(5usize, 2).checked_step_n(Direction4::Up, 3),
+            None
+        );
+        assert_eq!(
+            Point::new(usize::MAX, 0).checked_step(Direction4::Right),
+            None
+        );
+
+        assert_eq!(origin.step_within(Direction4::Left, 2, 2), None);
+        assert_eq!(
+            origin.step_within(Direction8::DownRight, 2, 2),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(
+            Point::new(1usize, 1).step_within(Direction4::Down, 2, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1usize, 6);
+        let b = Point::new(5usize, 11);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(b.manhattan_distance(a), 9);
+        assert_eq!(a.chebyshev_distance(b), 5);
+        assert_eq!(Point::new(-2i32, 3) - Point::new(1, 1), Point::new(-3, 2));
+    }
+}
@@ -0,0 +1,483 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::PuzzleError;
+
+/// Offsets of the four orthogonal neighbours, clockwise starting upwards.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting upwards.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A dense, rectangular 2D grid.
+///
+/// Cells are addressed by `(x, y)` positions, `x` being the column and `y` the row, starting with
+/// `(0, 0)` in the top left corner. Cells are stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from cells stored row by row.
+    /// Returns [`None`] if the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Self {
+                width,
+                height: 0,
+                cells,
+            });
+        }
+
+        cells.len().is_multiple_of(width).then(|| Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses a grid with one row per line, mapping every character to a cell with `map`.
+    ///
+    /// # Errors
+    /// Returns an error pointing at the first line whose length differs from the first line.
+    pub fn parse(input: &str, mut map: impl FnMut(char) -> T) -> Result<Self, PuzzleError> {
+        Self::try_parse(input, |c| Ok::<_, PuzzleError>(map(c)))
+    }
+
+    /// Parses a grid with one row per line, mapping every character to a cell with `map`.
+    ///
+    /// # Errors
+    /// Returns an error pointing at the first character `map` rejects or at the first line whose
+    /// length differs from the first line.
+    pub fn try_parse<E>(
+        input: &str,
+        mut map: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, PuzzleError>
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let mut width = None;
+        let mut cells = vec![];
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut length = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = map(c)
+                    .map_err(|e| PuzzleError::wrap(e).at_line(y + 1, line).at_column(x + 1))?;
+                cells.push(cell);
+                length += 1;
+            }
+
+            match width {
+                None => width = Some(length),
+                Some(width) if width != length => {
+                    return Err(PuzzleError::new(format!(
+                        "expected a row of width {width}, found {length}"
+                    ))
+                    .at_line(y + 1, line));
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` lies within the grid. Accepts signed positions, e.g. for stepping off an edge.
+    #[must_use]
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        usize::try_from(x).is_ok_and(|x| x < self.width)
+            && usize::try_from(y).is_ok_and(|y| y < self.height)
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// The cell at `position`, or [`None`] if it lies outside the grid.
+    #[must_use]
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    /// The cell at `position`, or [`None`] if it lies outside the grid.
+    #[must_use]
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `position` by `(dx, dy)`. Returns [`None`] if the new position lies outside the grid.
+    #[must_use]
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The positions of all cells, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells together with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell matching `predicate`, searching row by row.
+    #[must_use]
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The positions of the up to four orthogonal neighbours of `position` that lie within the grid,
+    /// clockwise starting upwards.
+    pub fn neighbours4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The positions of the up to eight neighbours of `position` (including diagonals) that lie
+    /// within the grid, clockwise starting upwards.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    #[must_use]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` rejects a chunk size of 0, an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// All columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Applies `f` to every cell.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid of the given size, calling `source` with each position of the new grid to get
+    /// the position of the cell in `self` to copy there.
+    fn rearrange(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    #[must_use]
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters.
+    ///
+    /// # Errors
+    /// Returns an error if the lines of `input` differ in length.
+    pub fn from_chars(input: &str) -> Result<Self, PuzzleError> {
+        Self::parse(input, |c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        let (x, y) = position;
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position ({x}, {y}) is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (x, y) = position;
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("position ({x}, {y}) is outside of the {width}x{height} grid")
+        })
+    }
+}
+
+/// Prints one line per row without separators between cells, e.g. the parsed input of a `Grid<char>`.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::PuzzleError;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn parses_grids() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let error = Grid::from_chars("abc\nde\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+
+        let error = Grid::try_parse("..\n.x", |c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            c => Err(PuzzleError::new(format!("unknown tile '{c}'"))),
+        })
+        .unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = Grid::new(3, 2, 0);
+
+        assert!(grid.contains(2, 1));
+        assert!(!grid.contains(-1, 0));
+        assert!(!grid.contains(0, 2));
+        assert_eq!(grid.offset((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "position (3, 0) is outside of the 3x2 grid")]
+    fn panics_when_indexing_out_of_bounds() {
+        let grid = Grid::new(3, 2, 0);
+        let _ = grid[(3, 0)];
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
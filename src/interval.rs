@@ -0,0 +1,252 @@
+use std::ops::Range;
+
+use num_integer::Integer;
+
+/// A set of integers stored as sorted, disjoint ranges.
+///
+/// Ranges are half-open like [`Range`]: `3..7` contains `3` to `6`. Overlapping and adjacent
+/// ranges are merged when they are inserted, so `1..3` and `3..5` become `1..5`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Integer + Copy,
+{
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges of the set in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, range| len + (range.end - range.start))
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// The largest value in the set.
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::one())
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(i).is_some_and(|range| range.start <= value)
+    }
+
+    /// Adds all values of `range` to the set. Empty ranges are ignored.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        // the ranges that overlap or touch `range` are merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first == last {
+            range
+        } else {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// The values that are in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    /// The values that are in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// The values of this set that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // skip the ranges of `other` that end before this range
+            while other.ranges.get(j).is_some_and(|r| r.end <= start) {
+                j += 1;
+            }
+
+            for cut in other.ranges[j..].iter().take_while(|r| r.start < range.end) {
+                if start < cut.start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Maps the set through a piecewise-linear function, splitting ranges where needed.
+    ///
+    /// Every piece `(source, target)` maps the values of `source` onto the range of the same
+    /// length starting at `target`, e.g. `(98..100, 50)` maps `98` to `50` and `99` to `51`.
+    /// Values covered by several pieces are mapped by the first one, values outside of all
+    /// pieces are kept as they are.
+    #[must_use]
+    pub fn map_pieces(&self, pieces: &[(Range<T>, T)]) -> Self {
+        let mut mapped = Self::new();
+        let mut unmapped = self.clone();
+
+        for (source, target) in pieces {
+            let piece = Self::from(source.clone());
+
+            for range in unmapped.intersection(&piece).ranges {
+                let start = range.start - source.start + *target;
+                mapped.insert(start..start + (range.end - range.start));
+            }
+
+            unmapped = unmapped.difference(&piece);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T>
+where
+    T: Integer + Copy,
+{
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Integer + Copy,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IntervalSet;
+
+    #[test]
+    fn coalesces_ranges() {
+        let set: IntervalSet<u32> = [5..8, 1..3, 3..4, 10..10, 7..9].into_iter().collect();
+
+        assert_eq!(set.ranges(), &[1..4, 5..9]);
+        assert_eq!(set.len(), 7);
+        assert_eq!(set.min(), Some(1));
+        assert_eq!(set.max(), Some(8));
+        assert!(set.contains(3));
+        assert!(!set.contains(4));
+        assert!(!set.contains(9));
+
+        let mut set = set;
+        set.insert(0..20);
+        assert_eq!(set, IntervalSet::from(0..20));
+        assert_eq!(IntervalSet::<i8>::new().min(), None);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: IntervalSet<i64> = [-5..0, 2..6, 10..15].into_iter().collect();
+        let b: IntervalSet<i64> = [-2..3, 5..11, 14..20].into_iter().collect();
+
+        assert_eq!(a.union(&b), IntervalSet::from(-5..20));
+        assert_eq!(
+            a.intersection(&b).ranges(),
+            &[-2..0, 2..3, 5..6, 10..11, 14..15]
+        );
+        assert_eq!(a.difference(&b).ranges(), &[-5..-2, 3..5, 11..14]);
+        assert_eq!(b.difference(&a).ranges(), &[0..2, 6..10, 15..20]);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+    }
+
+    #[test]
+    fn maps_pieces() {
+        // the seed-to-soil map of 2023 day 5
+        let pieces = [(98..100, 50), (50..98, 52)];
+
+        let seeds: IntervalSet<u64> = [79..93, 55..68].into_iter().collect();
+        assert_eq!(seeds.map_pieces(&pieces).ranges(), &[57..70, 81..95]);
+
+        let seeds = IntervalSet::from(40..100);
+        assert_eq!(seeds.map_pieces(&pieces), seeds);
+        assert_eq!(seeds.map_pieces(&pieces).len(), 60);
+
+        let seeds = IntervalSet::from(96..102);
+        // 96 and 97 are moved up next to 100 and 101, which are not mapped
+        assert_eq!(seeds.map_pieces(&pieces).ranges(), &[50..52, 98..102]);
+    }
+}
@@ -1,4 +1,21 @@
+pub mod cycle;
 mod day;
+mod error;
+mod geometry;
+mod grid;
+mod interval;
+pub mod math;
+pub mod parse;
+pub mod polygon;
+mod puzzle;
+pub mod search;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use error::*;
+pub use geometry::*;
+pub use grid::*;
+pub use interval::*;
+pub use puzzle::*;
+pub use year::*;
@@ -1,55 +1,138 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, verify, watch};
+use advent_of_code::template::input::InputSource;
+use advent_of_code::PuzzleId;
 use args::{parse, AppArguments};
 
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
     use std::process;
+    use std::time::Duration;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::extract::Pick;
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::report::OutputFormat;
+    use advent_of_code::{Day, Year};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Year,
+            pick: Pick,
         },
         Read {
             day: Day,
+            year: Year,
         },
         Scaffold {
             day: Day,
+            year: Option<Year>,
+            template: Option<String>,
+            pick: Pick,
         },
         Solve {
             day: Day,
+            year: Year,
             release: bool,
             time: bool,
+            format: OutputFormat,
             submit: Option<u8>,
+            timeout: Option<Duration>,
+            mem: bool,
+            watch: bool,
+            test: bool,
+            input: InputSource,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            format: OutputFormat,
+            jobs: Option<usize>,
+            timeout: Option<Duration>,
+            mem: bool,
+            save_baseline: Option<String>,
+            baseline: Option<String>,
+        },
+        Verify {
+            year: Year,
         },
     }
 
+    /// Parses a time limit given in (fractional) seconds.
+    fn parse_timeout(s: &str) -> Result<Duration, String> {
+        s.parse::<f64>()
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .filter(|timeout| !timeout.is_zero())
+            .ok_or_else(|| format!("expecting a positive number of seconds, got \"{s}\""))
+    }
+
+    /// Parses `--input <path>` or `--example [variant]`. Must run after all other options were
+    /// parsed, as the variant of an example is the last free argument.
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<InputSource, pico_args::Error> {
+        if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+            return Ok(InputSource::from_path(&path));
+        }
+
+        if args.contains("--example") {
+            let variant = args.opt_free_from_str()?.unwrap_or_default();
+            return Ok(InputSource::Example(variant));
+        }
+
+        Ok(InputSource::Puzzle)
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year: year.unwrap_or_default(),
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                jobs: args.opt_value_from_str("--jobs")?,
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                mem: args.contains("--mem"),
+                save_baseline: args.opt_value_from_str("--save-baseline")?,
+                baseline: args.opt_value_from_str("--baseline")?,
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                year: year.unwrap_or_default(),
+                pick: args.opt_value_from_str("--pick")?.unwrap_or_default(),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                year: year.unwrap_or_default(),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                year,
+                template: args.opt_value_from_str("--template")?,
+                pick: args.opt_value_from_str("--pick")?.unwrap_or_default(),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
+                year: year.unwrap_or_default(),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                mem: args.contains("--mem"),
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
+                input: parse_input(&mut args)?,
+            },
+            Some("verify") => AppArguments::Verify {
+                year: year.unwrap_or_default(),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +160,74 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+                format,
+                jobs,
+                timeout,
+                mem,
+                save_baseline,
+                baseline,
+            } => all::handle(
+                &solutions::registry(),
+                year,
+                release,
+                time,
+                format,
+                jobs,
+                timeout,
+                mem,
+                save_baseline,
+                baseline,
+            ),
+            AppArguments::Download { day, year, pick } => {
+                download::handle(PuzzleId::new(year, day), pick);
+            }
+            AppArguments::Read { day, year } => read::handle(PuzzleId::new(year, day)),
+            AppArguments::Scaffold {
+                day,
+                year,
+                template,
+                pick,
+            } => scaffold::handle(day, year, template.as_deref(), pick),
             AppArguments::Solve {
                 day,
+                year,
                 release,
                 time,
+                format,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                timeout,
+                mem,
+                watch,
+                test,
+                input,
+            } => {
+                let id = PuzzleId::new(year, day);
+
+                if watch {
+                    if submit.is_some() {
+                        eprintln!("Warning: `--submit` is ignored in watch mode.");
+                    }
+                    if input == InputSource::Stdin {
+                        eprintln!("Error: watch mode cannot read the input from stdin.");
+                        std::process::exit(1);
+                    }
+                    watch::handle(id, release, time, timeout, mem, &input, test);
+                } else {
+                    if test {
+                        eprintln!("Warning: `--test` requires `--watch`.");
+                    }
+                    if submit.is_some() && !input.is_puzzle() {
+                        eprintln!("Warning: `--submit` requires the puzzle input.");
+                    }
+                    let submit = submit.filter(|_| input.is_puzzle());
+                    solve::handle(id, release, time, format, submit, timeout, mem, &input);
+                }
+            }
+            AppArguments::Verify { year } => verify::handle(&solutions::registry(), year),
         },
     };
 }
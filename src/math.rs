@@ -0,0 +1,182 @@
+/// Module with number theory helpers for puzzles about things that repeat: least common
+/// multiples, modular arithmetic and the Chinese Remainder Theorem.
+///
+/// Modular functions take `i64`s and compute with `i128`s, so products of two residues never
+/// overflow. Results are always in `0..modulus`, also for negative inputs.
+use num_integer::Integer;
+
+/// The greatest common divisor of all `values`, `0` if there are none.
+pub fn gcd_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), |gcd, x| gcd.gcd(&x))
+}
+
+/// The least common multiple of all `values`, `1` if there are none.
+pub fn lcm_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), |lcm, x| lcm.lcm(&x))
+}
+
+/// Computes `base ^ exp % modulus` by repeated squaring.
+///
+/// # Panics
+/// Panics if `modulus` is not positive.
+#[must_use]
+pub fn mod_pow(base: i64, mut exp: u64, modulus: i64) -> i64 {
+    assert!(modulus > 0, "the modulus must be positive");
+
+    let modulus = i128::from(modulus);
+    let mut base = i128::from(base).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    narrow(result)
+}
+
+/// The `x` with `a * x % modulus == 1`. Returns [`None`] if `a` and `modulus` are not coprime.
+///
+/// # Panics
+/// Panics if `modulus` is not positive.
+#[must_use]
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    assert!(modulus > 0, "the modulus must be positive");
+    inverse(i128::from(a), i128::from(modulus)).map(narrow)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder
+/// Theorem. The moduli do not have to be coprime.
+///
+/// Returns the solution as `(x, modulus)`: every `x + k * modulus` solves all congruences.
+/// Returns [`None`] if the congruences contradict each other.
+///
+/// # Panics
+/// Panics if a modulus is not positive, or if the combined modulus does not fit into an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "the modulus must be positive");
+
+        let n = i128::from(modulus);
+        let a = i128::from(residue).rem_euclid(n);
+        let g = m.gcd(&n);
+
+        if (a - x) % g != 0 {
+            return None;
+        }
+
+        // solve x + m * k ≡ a (mod n) for k, which is unique modulo n / g
+        let n = n / g;
+        let k = (a - x) / g % n * inverse(m / g, n).expect("m / g and n / g are coprime");
+
+        x += m * k.rem_euclid(n);
+        m *= n;
+        x = x.rem_euclid(m);
+
+        assert!(
+            m <= i128::from(i64::MAX),
+            "the combined modulus does not fit into an i64"
+        );
+    }
+
+    Some((narrow(x), narrow(m)))
+}
+
+/// The first step at which all sequences `offset, offset + period, offset + 2 * period, ...`
+/// meet, e.g. the first step at which several looping paths are at their goal at once.
+///
+/// Unlike the least common multiple of the periods, the offsets do not have to be `0` or equal
+/// to the periods. Returns [`None`] if the sequences never meet.
+///
+/// # Panics
+/// Panics if a period is not positive or an offset is negative.
+pub fn align(sequences: impl IntoIterator<Item = (i64, i64)>) -> Option<i64> {
+    let sequences: Vec<(i64, i64)> = sequences.into_iter().collect();
+    assert!(
+        sequences.iter().all(|&(offset, _)| offset >= 0),
+        "offsets must not be negative"
+    );
+
+    let (x, modulus) = crt(sequences.iter().copied())?;
+
+    // every sequence only starts at its offset
+    let start = sequences
+        .iter()
+        .map(|&(offset, _)| offset)
+        .max()
+        .unwrap_or(0);
+    Some(x + Integer::div_ceil(&(start - x).max(0), &modulus) * modulus)
+}
+
+/// The inverse of `a` modulo `modulus` via the extended Euclidean algorithm.
+fn inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (mut old_r, mut r) = (a.rem_euclid(modulus), modulus);
+    let (mut old_s, mut s) = (1, 0);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+
+    (old_r == 1 || modulus == 1).then(|| old_s.rem_euclid(modulus))
+}
+
+/// Converts a result that is known to be smaller than an `i64` modulus back.
+fn narrow(value: i128) -> i64 {
+    i64::try_from(value).expect("results are smaller than the modulus")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{align, crt, gcd_all, lcm_all, mod_inverse, mod_pow};
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd_all([12u64, 18, 30]), 6);
+        assert_eq!(lcm_all([4u64, 6, 10]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+
+        let naive = (0..200).fold(1, |x, _| x * 3 % 1_000_000_007);
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), naive);
+        assert_eq!(
+            mod_pow(i64::MAX - 1, u64::MAX, i64::MAX),
+            mod_pow(-1, 1, i64::MAX)
+        );
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(-1, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn aligns_sequences() {
+        // the least common multiple for sequences without an offset
+        assert_eq!(align([(3, 3), (5, 5)]), Some(15));
+        assert_eq!(align([(5, 3), (1, 4)]), Some(5));
+        assert_eq!(align([(7, 2), (0, 3)]), Some(9));
+        assert_eq!(align([(1, 2), (0, 4)]), None);
+    }
+}
@@ -0,0 +1,320 @@
+/// Module with helpers for the input shapes that come up in most puzzles: numbers, lists,
+/// `key: values` lines, blank-line separated blocks and labelled sections.
+///
+/// The helpers report failures as [`PuzzleError`]s that point at the offending line and column.
+/// Errors of helpers that work on a single line are located within the whole input when used
+/// through [`parse_lines`](crate::parse_lines), [`Block::parse`] or [`Block::section`].
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::PuzzleError;
+
+/// Parses `s` as a number, ignoring surrounding whitespace.
+///
+/// # Errors
+/// Returns an error pointing at the number if it cannot be parsed as a `T`.
+pub fn int<T>(s: &str) -> Result<T, PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let start = s.len() - s.trim_start().len();
+    token(s, start, s.trim())
+}
+
+/// Parses a list of numbers separated by whitespace and/or commas, e.g. `1, 2, 3` or `4 5 6`.
+///
+/// # Errors
+/// Returns an error pointing at the first item that cannot be parsed as a `T`.
+pub fn list<T>(s: &str) -> Result<Vec<T>, PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    list_from(s, 0)
+}
+
+/// Extracts every number from `s`, skipping any other text. A `-` directly in front of a number
+/// is read as its sign, so `x=-3..4` yields `-3` and `4`.
+///
+/// # Errors
+/// Returns an error pointing at the first number that cannot be parsed as a `T`, e.g. because it
+/// is negative or too large.
+pub fn ints<T>(s: &str) -> Result<Vec<T>, PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let bytes = s.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        numbers.push(token(s, start, &s[start..i])?);
+    }
+
+    Ok(numbers)
+}
+
+/// Parses a `key: values` line, e.g. `Time:  7  15  30`, into its key and its list of values.
+///
+/// # Errors
+/// Returns an error if the line has no `:` or one of the values cannot be parsed as a `T`.
+pub fn key_values<T>(line: &str) -> Result<(&str, Vec<T>), PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let (key, _) = line
+        .split_once(':')
+        .ok_or_else(|| PuzzleError::at_offset(line, line.len(), "expected a `key: values` line"))?;
+
+    Ok((key.trim(), list_from(line, key.len() + 1)?))
+}
+
+/// A group of lines separated from other groups by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The lines of the block, without the trailing newline.
+    pub text: &'a str,
+    /// The number of the first line of the block within the input, starting at 1.
+    pub line: usize,
+}
+
+impl<'a> Block<'a> {
+    /// Parses the text of the block with `parse`, locating errors within the whole input.
+    ///
+    /// # Errors
+    /// Returns the error of `parse`. Errors without a line point at the first line of the block.
+    pub fn parse<T, E>(&self, parse: impl FnOnce(&'a str) -> Result<T, E>) -> Result<T, PuzzleError>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        parse(self.text).map_err(|e| self.locate(PuzzleError::wrap(e), 0))
+    }
+
+    /// Parses a labelled section such as
+    ///
+    /// ```text
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// 52 50 48
+    /// ```
+    ///
+    /// by passing its label without the trailing `:` and the lines below it to `parse`.
+    ///
+    /// # Errors
+    /// Returns an error if the first line of the block does not end with `:`, or the error of
+    /// `parse`. Errors without a line point at the label.
+    pub fn section<T, E>(
+        &self,
+        parse: impl FnOnce(&'a str, &'a str) -> Result<T, E>,
+    ) -> Result<T, PuzzleError>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let (first, body) = self.text.split_once('\n').unwrap_or((self.text, ""));
+        let label = first.trim_end().strip_suffix(':').ok_or_else(|| {
+            let error = PuzzleError::at_offset(first, first.len(), "expected a section label");
+            self.locate(error, 0)
+        })?;
+
+        parse(label, body).map_err(|e| self.locate(PuzzleError::wrap(e), 1))
+    }
+
+    /// Locates an error of the block's text, or of its text starting at line `skip`.
+    fn locate(&self, error: PuzzleError, skip: usize) -> PuzzleError {
+        if error.line().is_some() {
+            return error.offset_lines(self.line - 1 + skip);
+        }
+
+        error.at_line(self.line, self.text.lines().next().unwrap_or_default())
+    }
+}
+
+/// Splits `input` into blocks of lines separated by one or more blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut offset = 0;
+    let mut lines = input.split_inclusive('\n').map(move |line| {
+        let start = offset;
+        offset += line.len();
+        (start, line)
+    });
+    let mut line_number = 0;
+
+    std::iter::from_fn(move || {
+        let (start, first) = lines.by_ref().find(|(_, line)| {
+            line_number += 1;
+            !line.trim().is_empty()
+        })?;
+        let block_line = line_number;
+        let mut end = start + first.len();
+
+        for (start, line) in lines.by_ref() {
+            line_number += 1;
+            if line.trim().is_empty() {
+                break;
+            }
+            end = start + line.len();
+        }
+
+        Some(Block {
+            text: input[start..end].trim_end(),
+            line: block_line,
+        })
+    })
+}
+
+/// Parses every labelled section of `input`, see [`Block::section`].
+///
+/// # Errors
+/// Returns the first error of a section.
+pub fn sections<'a, T, E>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str, &'a str) -> Result<T, E>,
+) -> Result<Vec<T>, PuzzleError>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    blocks(input)
+        .map(|block| block.section(&mut parse))
+        .collect()
+}
+
+/// Parses the list starting at byte `start` of `s`, e.g. the part of a line behind a label or a
+/// separator. Unlike slicing `s` and using [`list`], errors point at their column within all of `s`.
+///
+/// # Errors
+/// Returns an error pointing at the first item that cannot be parsed as a `T`.
+///
+/// # Panics
+/// Panics if `start` is not a character boundary of `s`.
+pub fn list_from<T>(s: &str, start: usize) -> Result<Vec<T>, PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    s[start..]
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .scan(start, |offset, item| {
+            let item_start = *offset;
+            *offset += item.len() + 1;
+            Some((item_start, item))
+        })
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(offset, item)| token(s, offset, item))
+        .collect()
+}
+
+/// Parses `token`, which starts at byte `offset` of `s`.
+fn token<T>(s: &str, offset: usize, token: &str) -> Result<T, PuzzleError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    token.parse().map_err(|e| {
+        PuzzleError::at_offset(s, offset, format!("invalid number \"{token}\"")).with_source(e)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, int, ints, key_values, list, list_from, sections, Block};
+    use crate::parse_lines;
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(int::<u8>(" 42 ").unwrap(), 42);
+        assert_eq!(int::<i64>("-7").unwrap(), -7);
+        assert_eq!(list::<u32>("1, 2,3  4").unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(list::<i32>("").unwrap(), Vec::<i32>::new());
+        assert_eq!(ints::<i32>("x=-3..4, y=10-2").unwrap(), vec![-3, 4, 10, -2]);
+        assert_eq!(
+            key_values::<u64>("Time:      7  15   30").unwrap(),
+            ("Time", vec![7, 15, 30])
+        );
+    }
+
+    #[test]
+    fn locates_number_errors() {
+        let error = list::<u8>("1 2 300").unwrap_err();
+        assert_eq!(error.column(), Some(5));
+        assert_eq!(error.message(), "invalid number \"300\"");
+
+        let error = ints::<u32>("a=1, b=-2").unwrap_err();
+        assert_eq!(error.column(), Some(8));
+
+        let error = parse_lines("a: 1 2\nb: 3 x\n", key_values::<u32>).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(6));
+
+        let error = list_from::<u8>("1 | 2 x", 3).unwrap_err();
+        assert_eq!(error.column(), Some(7));
+        assert_eq!(list_from::<u8>("1 | 2 3", 3).unwrap(), vec![2, 3]);
+
+        let error = key_values::<u32>("1 2 3").unwrap_err();
+        assert_eq!(error.message(), "expected a `key: values` line");
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "a\nb\n\n\nc\n\nd\ne\n";
+        let blocks: Vec<Block> = blocks(input).collect();
+
+        assert_eq!(
+            blocks,
+            vec![
+                Block {
+                    text: "a\nb",
+                    line: 1
+                },
+                Block { text: "c", line: 5 },
+                Block {
+                    text: "d\ne",
+                    line: 7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_sections() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil map:\n0 15 37\n";
+        let mut blocks = blocks(input);
+
+        let seeds = blocks.next().unwrap().parse(key_values::<u64>).unwrap();
+        assert_eq!(seeds, ("seeds", vec![79, 14]));
+
+        let maps: Vec<(&str, Vec<u64>)> = blocks
+            .map(|block| block.section(|label, body| Ok::<_, String>((label, list(body).unwrap()))))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(maps[0], ("seed-to-soil map", vec![50, 98, 2, 52, 50, 48]));
+        assert_eq!(maps[1], ("soil map", vec![0, 15, 37]));
+    }
+
+    #[test]
+    fn locates_section_errors() {
+        let input = "a map:\n1 2\n\nb map:\n3 4\n5 x\n";
+        let error = sections(input, |_, body| parse_lines(body, list::<u32>)).unwrap_err();
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.column(), Some(3));
+
+        let error = sections("a map:\n1\n\nb\n2\n", |_, body| list::<u32>(body)).unwrap_err();
+        assert_eq!(error.line(), Some(4));
+        assert_eq!(error.message(), "expected a section label");
+    }
+}
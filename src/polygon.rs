@@ -0,0 +1,199 @@
+/// Module with area computations for polygons with integer vertices, e.g. a loop through a grid or
+/// the outline of a dug out trench.
+///
+/// A polygon is given by its vertices in order around its boundary, clockwise or counterclockwise,
+/// and its last vertex connects back to the first. Vertices may repeat the first vertex at the end
+/// and may lie in the middle of an edge, e.g. when every tile of a loop is passed in. The polygon
+/// must not cross itself.
+///
+/// Coordinates are `i64`s and results are computed as `i128`s, so areas far larger than an `i64`
+/// can hold do not overflow. [Pick's theorem] relates the area to the number of lattice points,
+/// the points with integer coordinates, inside and on the boundary of the polygon.
+///
+/// [Pick's theorem]: https://en.wikipedia.org/wiki/Pick%27s_theorem
+use num_integer::Integer;
+
+use crate::Point;
+
+/// Twice the area of the polygon, computed with the shoelace formula. Polygons with integer
+/// vertices can have an area of half a unit, twice the area is always an integer.
+#[must_use]
+pub fn double_area(vertices: &[Point<i64>]) -> i128 {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i128>()
+        .abs()
+}
+
+/// The area of the polygon, rounded down to a whole unit. Use [`double_area`] for the exact area.
+#[must_use]
+pub fn area(vertices: &[Point<i64>]) -> i128 {
+    double_area(vertices) / 2
+}
+
+/// The number of lattice points on the boundary of the polygon. For polygons with only horizontal
+/// and vertical edges, this is the length of the boundary.
+#[must_use]
+pub fn boundary_points(vertices: &[Point<i64>]) -> i128 {
+    edges(vertices)
+        .map(|(a, b)| (b.x - a.x).abs().gcd(&(b.y - a.y).abs()))
+        .sum()
+}
+
+/// The number of lattice points strictly inside the polygon, e.g. the tiles enclosed by a loop
+/// through the centres of grid tiles.
+#[must_use]
+pub fn interior_points(vertices: &[Point<i64>]) -> i128 {
+    // Pick's theorem: area = interior + boundary / 2 - 1
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// The number of lattice points inside or on the boundary of the polygon, e.g. the tiles covered
+/// by a loop through the centres of grid tiles together with the tiles it encloses.
+#[must_use]
+pub fn lattice_points(vertices: &[Point<i64>]) -> i128 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// The edges of the polygon, with the coordinates of their ends widened to `i128`s.
+fn edges(vertices: &[Point<i64>]) -> impl Iterator<Item = (Point<i128>, Point<i128>)> + '_ {
+    let widen = |p: &Point<i64>| Point::new(i128::from(p.x), i128::from(p.y));
+
+    vertices
+        .iter()
+        .map(widen)
+        .zip(vertices.iter().cycle().skip(1).map(widen))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{area, boundary_points, double_area, interior_points, lattice_points};
+    use crate::{Direction4, Point};
+
+    /// The loop of the first part two example of 2023 day 10, one vertex per pipe corner.
+    const PIPE_LOOP: [(i64, i64); 12] = [
+        (1, 1),
+        (9, 1),
+        (9, 7),
+        (6, 7),
+        (6, 5),
+        (8, 5),
+        (8, 2),
+        (2, 2),
+        (2, 5),
+        (4, 5),
+        (4, 7),
+        (1, 7),
+    ];
+
+    /// The dig plan of the 2023 day 18 example as directions and distances.
+    const DIG_PLAN: [(char, i64); 14] = [
+        ('R', 6),
+        ('D', 5),
+        ('L', 2),
+        ('D', 2),
+        ('R', 2),
+        ('D', 2),
+        ('L', 5),
+        ('U', 2),
+        ('L', 1),
+        ('U', 2),
+        ('R', 2),
+        ('U', 3),
+        ('L', 2),
+        ('U', 2),
+    ];
+
+    /// The colours of the day 18 example, which encode a much larger dig plan.
+    const COLOURS: [&str; 14] = [
+        "70c710", "0dc571", "5713f0", "d2c081", "59c680", "411b91", "8ceee2", "caa173", "1b58a2",
+        "caa171", "7807d2", "a77fa3", "015232", "7a21e3",
+    ];
+
+    fn points(vertices: &[(i64, i64)]) -> Vec<Point<i64>> {
+        vertices.iter().map(|&v| Point::from(v)).collect()
+    }
+
+    fn dig(plan: impl IntoIterator<Item = (Direction4, i64)>) -> Vec<Point<i64>> {
+        plan.into_iter()
+            .scan(Point::new(0, 0), |position, (direction, distance)| {
+                *position = position.step_n(direction, distance);
+                Some(*position)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measures_simple_shapes() {
+        let square = points(&[(0, 0), (3, 0), (3, 3), (0, 3)]);
+        assert_eq!(double_area(&square), 18);
+        assert_eq!(area(&square), 9);
+        assert_eq!(boundary_points(&square), 12);
+        assert_eq!(interior_points(&square), 4);
+        assert_eq!(lattice_points(&square), 16);
+
+        let triangle = points(&[(0, 0), (0, 4), (4, 0)]);
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+
+        let half = points(&[(0, 0), (1, 0), (0, 1), (0, 0)]);
+        assert_eq!(double_area(&half), 1);
+        assert_eq!(area(&half), 0);
+        assert_eq!(interior_points(&half), 0);
+    }
+
+    #[test]
+    fn counts_enclosed_tiles() {
+        let pipes = points(&PIPE_LOOP);
+        assert_eq!(boundary_points(&pipes), 46);
+        assert_eq!(interior_points(&pipes), 4);
+
+        // the same loop with a vertex for every tile, in the opposite direction
+        let mut tiles = Vec::new();
+        for (i, &a) in pipes.iter().enumerate() {
+            let b = pipes[(i + 1) % pipes.len()];
+            let step = Point::new((b.x - a.x).signum(), (b.y - a.y).signum());
+            tiles.extend((0..a.manhattan_distance(b)).map(|n| a + step * n));
+        }
+        tiles.reverse();
+
+        assert_eq!(tiles.len(), 46);
+        assert_eq!(double_area(&tiles), double_area(&pipes));
+        assert_eq!(interior_points(&tiles), 4);
+    }
+
+    #[test]
+    fn measures_dug_trenches() {
+        let plan = DIG_PLAN.map(|(c, distance)| (Direction4::try_from(c).unwrap(), distance));
+        let trench = dig(plan);
+        assert_eq!(boundary_points(&trench), 38);
+        assert_eq!(lattice_points(&trench), 62);
+
+        let plan = COLOURS.map(|colour| {
+            let direction = [
+                Direction4::Right,
+                Direction4::Down,
+                Direction4::Left,
+                Direction4::Up,
+            ];
+            let distance = i64::from_str_radix(&colour[..5], 16).unwrap();
+            (
+                direction[usize::from(colour.as_bytes()[5] - b'0')],
+                distance,
+            )
+        });
+        assert_eq!(lattice_points(&dig(plan)), 952_408_144_115);
+    }
+
+    #[test]
+    fn does_not_overflow() {
+        let max = i64::MAX;
+        let square = points(&[(0, 0), (max, 0), (max, max), (0, max)]);
+
+        let side = i128::from(max);
+        assert_eq!(double_area(&square), 2 * side * side);
+        assert_eq!(boundary_points(&square), 4 * side);
+        assert_eq!(interior_points(&square), (side - 1) * (side - 1));
+    }
+}
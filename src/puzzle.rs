@@ -0,0 +1,39 @@
+use std::fmt::Display;
+
+use crate::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the solution binary of
+/// puzzles that were scaffolded for a specific year.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId, Year};
+/// let id = PuzzleId::new(Year::new(2022).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(id.to_string(), "2022-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+}
+
+/// A [`Day`] without a year refers to the puzzle of that day in the default [`Year`].
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::new(Year::default(), day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
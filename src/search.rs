@@ -0,0 +1,330 @@
+/// Module with shortest path searches over implicit graphs.
+///
+/// Instead of building a graph up front, the searches explore it from a start state: `successors`
+/// returns the states reachable from a state (with the cost of the move for weighted searches) and
+/// `is_goal` tells whether a state ends the search. A goal predicate instead of a goal state
+/// allows for multiple goals and for goals that only match part of a state, e.g. its position.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    /// The total cost of all moves along the path.
+    pub cost: C,
+    /// All states along the path, from the start to the goal.
+    pub states: Vec<S>,
+}
+
+impl<S, C> Path<S, C> {
+    /// The goal the path ends in.
+    #[must_use]
+    pub fn goal(&self) -> &S {
+        self.states
+            .last()
+            .expect("a path contains at least its start")
+    }
+}
+
+/// The states visited by a search, each with the index of the state it was reached from.
+struct Visited<S, C> {
+    index: HashMap<S, usize>,
+    nodes: Vec<(S, Option<usize>, C)>,
+}
+
+impl<S: Clone + Eq + Hash, C> Visited<S, C> {
+    fn new(start: S, cost: C) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, None, cost)],
+        }
+    }
+
+    fn path(&self, mut i: usize) -> Vec<S> {
+        let mut states = vec![self.nodes[i].0.clone()];
+
+        while let Some(parent) = self.nodes[i].1 {
+            states.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+
+        states.reverse();
+        states
+    }
+}
+
+/// Finds a path with the lowest cost from `start` to a state that `is_goal` accepts.
+/// Costs of moves must not be negative.
+///
+/// Returns [`None`] if no goal can be reached.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Finds a path with the lowest cost from `start` to a state that `is_goal` accepts, exploring
+/// states with a low cost plus `heuristic` first. The heuristic must never overestimate the
+/// remaining cost to the nearest goal, e.g. the Manhattan distance on a grid where every move
+/// costs at least 1.
+///
+/// Returns [`None`] if no goal can be reached.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut visited = Visited::new(start, C::default());
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        let (state, _, best) = &visited.nodes[i];
+
+        // the state was reached more cheaply after it was queued
+        if cost > *best {
+            continue;
+        }
+
+        if is_goal(state) {
+            return Some(Path {
+                cost,
+                states: visited.path(i),
+            });
+        }
+
+        for (next, step) in successors(state) {
+            let next_cost = cost + step;
+
+            let j = match visited.index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let j = *entry.get();
+                    if next_cost >= visited.nodes[j].2 {
+                        continue;
+                    }
+                    visited.nodes[j].1 = Some(i);
+                    visited.nodes[j].2 = next_cost;
+                    j
+                }
+                Entry::Vacant(entry) => {
+                    let j = visited.nodes.len();
+                    visited
+                        .nodes
+                        .push((entry.key().clone(), Some(i), next_cost));
+                    entry.insert(j);
+                    j
+                }
+            };
+
+            let estimate = next_cost + heuristic(&visited.nodes[j].0);
+            queue.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/// Finds a path with the fewest moves from `start` to a state that `is_goal` accepts.
+/// The cost of the path is its number of moves.
+///
+/// Returns [`None`] if no goal can be reached.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::from([0]);
+    let mut visited = Visited::new(start, 0);
+
+    while let Some(i) = queue.pop_front() {
+        let (state, _, moves) = &visited.nodes[i];
+        let moves = *moves;
+
+        if is_goal(state) {
+            return Some(Path {
+                cost: moves,
+                states: visited.path(i),
+            });
+        }
+
+        for next in successors(state) {
+            if let Entry::Vacant(entry) = visited.index.entry(next) {
+                let j = visited.nodes.len();
+                visited
+                    .nodes
+                    .push((entry.key().clone(), Some(i), moves + 1));
+                entry.insert(j);
+                queue.push_back(j);
+            }
+        }
+    }
+
+    None
+}
+
+/// The lowest cost of reaching every state that can be reached from `start`.
+pub fn dijkstra_all<S, C, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut queue = BinaryHeap::from([Reverse((C::default(), 0))]);
+    let mut states = vec![start];
+
+    while let Some(Reverse((cost, i))) = queue.pop() {
+        if cost > costs[&states[i]] {
+            continue;
+        }
+
+        for (next, step) in successors(&states[i]) {
+            let next_cost = cost + step;
+
+            match costs.entry(next) {
+                Entry::Occupied(mut entry) if next_cost < *entry.get() => {
+                    entry.insert(next_cost);
+                    states.push(entry.key().clone());
+                }
+                Entry::Occupied(_) => continue,
+                Entry::Vacant(entry) => {
+                    states.push(entry.key().clone());
+                    entry.insert(next_cost);
+                }
+            }
+
+            queue.push(Reverse((next_cost, states.len() - 1)));
+        }
+    }
+
+    costs
+}
+
+/// The fewest moves needed to reach every state that can be reached from `start`.
+pub fn bfs_all<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut moves = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, n)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = moves.entry(next) {
+                queue.push_back((entry.key().clone(), n + 1));
+                entry.insert(n + 1);
+            }
+        }
+    }
+
+    moves
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_all, dijkstra, dijkstra_all};
+    use crate::Grid;
+
+    const MAZE: &str = "\
+..#....
+..#.##.
+....#..
+###.#.#
+....#..";
+
+    fn open_neighbours(grid: &Grid<char>, position: (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbours4(position)
+            .filter(|&p| grid[p] == '.')
+            .collect()
+    }
+
+    fn graph(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('c', 1), ('d', 7)],
+            'c' => vec![('d', 2)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let path = dijkstra('a', graph, |&n| n == 'd').unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(path.goal(), &'d');
+
+        let path = dijkstra('a', graph, |&n| n == 'a').unwrap();
+        assert_eq!(path.cost, 0);
+        assert_eq!(path.states, vec!['a']);
+
+        assert_eq!(dijkstra('c', graph, |&n| n == 'a'), None);
+    }
+
+    #[test]
+    fn finds_paths_with_heuristic() {
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let goal: (usize, usize) = (6, 4);
+        let distance = |&(x, y): &(usize, usize)| goal.0.abs_diff(x) + goal.1.abs_diff(y);
+
+        let successors =
+            |&p: &(usize, usize)| open_neighbours(&grid, p).into_iter().map(|next| (next, 1));
+
+        let path = astar((0, 0), successors, distance, |&p| p == goal).unwrap();
+        let expected = dijkstra((0, 0), successors, |&p| p == goal).unwrap();
+
+        assert_eq!(path.cost, 16);
+        assert_eq!(path.cost, expected.cost);
+        assert_eq!(path.states.len(), 17);
+        assert!(path
+            .states
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|p| p == w[1])));
+    }
+
+    #[test]
+    fn finds_nearest_goal() {
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let successors = |&p: &(usize, usize)| open_neighbours(&grid, p);
+
+        let path = bfs((0, 0), successors, |&p| p == (6, 0) || p == (1, 4)).unwrap();
+        assert_eq!(path.cost, 9);
+        assert_eq!(path.goal(), &(1, 4));
+
+        assert_eq!(bfs((0, 0), successors, |&p| p == (2, 0)), None);
+    }
+
+    #[test]
+    fn finds_all_costs() {
+        let costs = dijkstra_all('a', graph);
+        assert_eq!(costs.len(), 4);
+        assert_eq!(costs[&'c'], 2);
+        assert_eq!(costs[&'d'], 4);
+
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let moves = bfs_all((0, 0), |&p| open_neighbours(&grid, p));
+        assert_eq!(moves[&(3, 0)], 7);
+        assert_eq!(moves.values().max(), Some(&16));
+        assert!(!moves.contains_key(&(2, 0)));
+    }
+}
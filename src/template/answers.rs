@@ -0,0 +1,134 @@
+/// Module that stores the known answers to the real puzzle inputs in `data/<year>/answers/`.
+/// Each day has one file with one line per part, e.g. `Part 1: 42`.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+/// The known answers for both parts of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path(id: PuzzleId) -> PathBuf {
+    get_data_path("answers", id.year, &format!("{}.txt", id.day))
+}
+
+pub(crate) fn parse(s: &str) -> Answers {
+    let mut answers = Answers::default();
+
+    for line in s.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        match key.trim() {
+            "Part 1" => answers.set(1, value),
+            "Part 2" => answers.set(2, value),
+            _ => {}
+        }
+    }
+
+    answers
+}
+
+pub(crate) fn format(answers: &Answers) -> String {
+    [(1, &answers.part_1), (2, &answers.part_2)]
+        .into_iter()
+        .filter_map(|(part, answer)| Some(format!("Part {part}: {}\n", answer.as_ref()?)))
+        .collect()
+}
+
+/// Reads the known answers for a day. Returns empty [`Answers`] if none have been recorded yet.
+#[must_use]
+pub fn read(id: PuzzleId) -> Answers {
+    fs::read_to_string(get_path(id))
+        .map(|s| parse(&s))
+        .unwrap_or_default()
+}
+
+/// Records the answer for one part of a day, keeping the answer of the other part.
+pub fn store(id: PuzzleId, part: u8, answer: &str) -> Result<(), io::Error> {
+    let path = get_path(id);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut answers = read(id);
+    answers.set(part, answer);
+    fs::write(path, format(&answers))
+}
+
+/// Compares an answer against the known answer for a part.
+/// Returns [`None`] if no answer has been recorded for this part.
+#[must_use]
+pub fn check(answers: &Answers, part: u8, answer: Option<&str>) -> Option<bool> {
+    let expected = answers.get(part)?;
+    Some(answer.is_some_and(|answer| answer.trim() == expected))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, format, parse, Answers};
+
+    #[test]
+    fn parse_answers() {
+        let answers = parse("Part 1: 55130\nPart 2: 54985\n");
+        assert_eq!(answers.get(1), Some("55130"));
+        assert_eq!(answers.get(2), Some("54985"));
+    }
+
+    #[test]
+    fn parse_partial_answers() {
+        let answers = parse("Part 2: abc\n\nPart 1:\n");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("abc"));
+    }
+
+    #[test]
+    fn format_roundtrip() {
+        let answers = Answers {
+            part_1: None,
+            part_2: Some("42".into()),
+        };
+        assert_eq!(format(&answers), "Part 2: 42\n");
+        assert_eq!(parse(&format(&answers)), answers);
+    }
+
+    #[test]
+    fn check_answers() {
+        let answers = parse("Part 1: 42\n");
+        assert_eq!(check(&answers, 1, Some("42")), Some(true));
+        assert_eq!(check(&answers, 1, Some("41")), Some(false));
+        assert_eq!(check(&answers, 1, None), Some(false));
+        assert_eq!(check(&answers, 2, Some("42")), None);
+    }
+}
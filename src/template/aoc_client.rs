@@ -0,0 +1,433 @@
+/// Built-in client for the Advent of Code website.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or, if that is not set,
+/// from an `.adventofcode.session` file in the home directory.
+/// The base URL defaults to `https://adventofcode.com` and can be changed via `AOC_BASE_URL`.
+use std::{env, fmt::Display, fs, io, path::PathBuf, sync::LazyLock, time::Duration};
+
+use regex::Regex;
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+static ARTICLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<article[^>]*>.*?</article>").unwrap());
+static BLOCK_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"</?(p|h2|pre|ul|li|article)[^>]*>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]+>").unwrap());
+static BLANK_LINES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+static WAIT_MINUTES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"wait (one|\d+) minutes?").unwrap());
+static TIME_LEFT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    BadStatus(u16),
+    Network(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found, set AOC_SESSION or create ~/.adventofcode.session."
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::Network(e) => write!(f, "request failed: {e}"),
+            AocClientError::IoError(e) => {
+                write!(f, "could not write output files to file system: {e}")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Network(e.to_string()),
+        }
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    /// The answer is wrong. `wait` is the time until the next answer is accepted.
+    Wrong {
+        wait: Option<Duration>,
+    },
+    TooHigh {
+        wait: Option<Duration>,
+    },
+    TooLow {
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently, nothing was checked.
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Creates a client configured by the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, id: PuzzleId) -> String {
+        format!("{}/{}/day/{}", self.base_url, id.year, id.day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = ureq::get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the input of a puzzle.
+    pub fn get_input(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(id)))
+    }
+
+    /// Fetches the description of a puzzle as the HTML of its `<article>` elements.
+    pub fn get_puzzle(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(id))?;
+        Ok(extract_articles(&html).join("\n\n"))
+    }
+
+    /// Submits an answer and returns the verdict together with the message of the server.
+    pub fn submit(
+        &self,
+        id: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<(SubmissionOutcome, String), AocClientError> {
+        let response = ureq::post(&format!("{}/answer", self.day_url(id)))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        let message = html_to_text(&extract_articles(&html).join("\n"));
+        Ok((parse_submission_response(&message), message))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn download(id: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_data_path("inputs", id.year, &format!("{}.txt", id.day));
+    let puzzle_path = get_data_path("puzzles", id.year, &format!("{}.md", id.day));
+
+    let input = client.get_input(id)?;
+    let puzzle = client.get_puzzle(id)?;
+
+    for path in [&input_path, &puzzle_path] {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, puzzle)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn read(id: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.get_puzzle(id)?;
+    println!("{}", html_to_text(&puzzle));
+    Ok(())
+}
+
+pub fn submit(id: PuzzleId, part: u8, result: &str) -> Result<SubmissionOutcome, AocClientError> {
+    let client = AocClient::from_env()?;
+    let (outcome, message) = client.submit(id, part, result)?;
+    println!("{message}");
+    Ok(outcome)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    let session = fs::read_to_string(path).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub(crate) fn extract_articles(html: &str) -> Vec<&str> {
+    ARTICLE.find_iter(html).map(|m| m.as_str()).collect()
+}
+
+/// Converts a HTML fragment to plain text by dropping all tags and decoding common entities.
+#[must_use]
+pub fn html_to_text(html: &str) -> String {
+    let text = BLOCK_TAG.replace_all(html, "\n");
+    let text = decode_entities(&TAG.replace_all(&text, ""));
+
+    BLANK_LINES.replace_all(text.trim(), "\n\n").to_string()
+}
+
+/// Decodes the HTML entities that occur in puzzle descriptions.
+pub(crate) fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Parses durations like "one minute", "5 minutes" or "1m 23s".
+fn parse_wait(s: &str) -> Option<Duration> {
+    if let Some(captures) = WAIT_MINUTES.captures(s) {
+        let minutes = match &captures[1] {
+            "one" => 1,
+            n => n.parse().ok()?,
+        };
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    let captures = TIME_LEFT.captures(s)?;
+    let minutes: u64 = captures
+        .get(1)
+        .map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let seconds: u64 = captures[2].parse().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+/// Interprets the message the server responds with after submitting an answer.
+#[must_use]
+pub fn parse_submission_response(message: &str) -> SubmissionOutcome {
+    let wait = parse_wait(message);
+
+    if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("You gave an answer too recently") {
+        SubmissionOutcome::RateLimited {
+            wait: wait.unwrap_or_default(),
+        }
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            SubmissionOutcome::TooHigh { wait }
+        } else if message.contains("your answer is too low") {
+            SubmissionOutcome::TooLow { wait }
+        } else {
+            SubmissionOutcome::Wrong { wait }
+        }
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
+    } else {
+        SubmissionOutcome::Unknown
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use super::{html_to_text, parse_submission_response, AocClient, SubmissionOutcome};
+    use crate::{day, year, PuzzleId};
+
+    fn puzzle_id(day: crate::Day) -> PuzzleId {
+        PuzzleId::new(year!(2023), day)
+    }
+
+    /// Serves a single HTTP request with `body` and returns the received request head and body.
+    fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = serve_once(200, "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&base_url, "secret");
+
+        let input = client.get_input(puzzle_id(day!(1))).unwrap();
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn downloads_puzzle_articles() {
+        let (base_url, server) = serve_once(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi</p></article><p>Answer:</p></main></html>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let puzzle = client.get_puzzle(puzzle_id(day!(1))).unwrap();
+        assert_eq!(
+            puzzle,
+            "<article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi</p></article>"
+        );
+        assert!(server.join().unwrap().starts_with("GET /2023/day/1 "));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, server) = serve_once(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low. Please wait one minute before trying again.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let (outcome, message) = client.submit(puzzle_id(day!(7)), 2, "123").unwrap();
+        assert_eq!(
+            outcome,
+            SubmissionOutcome::TooLow {
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert!(message.starts_with("That's not the right answer"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=123"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve_once(404, "not found");
+        let client = AocClient::new(&base_url, "secret");
+        assert!(client.get_input(puzzle_id(day!(25))).is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn parses_submission_responses() {
+        assert_eq!(
+            parse_submission_response("That's the right answer! You are one gold star closer."),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            parse_submission_response("That's not the right answer. If you're stuck, please wait 5 minutes before trying again."),
+            SubmissionOutcome::Wrong {
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_submission_response("That's not the right answer; your answer is too high."),
+            SubmissionOutcome::TooHigh { wait: None }
+        );
+        assert_eq!(
+            parse_submission_response(
+                "You gave an answer too recently. You have 1m 23s left to wait."
+            ),
+            SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(83)
+            }
+        );
+        assert_eq!(
+            parse_submission_response(
+                "You gave an answer too recently. You have 42s left to wait."
+            ),
+            SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(42)
+            }
+        );
+        assert_eq!(
+            parse_submission_response("You don't seem to be solving the right level."),
+            SubmissionOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn converts_html_to_text() {
+        let text = html_to_text(
+            "<article><h2>--- Day 1 ---</h2><p>a &lt; <em>b</em> &amp; c</p></article>",
+        );
+        assert_eq!(text, "--- Day 1 ---\n\na < b & c");
+    }
+}
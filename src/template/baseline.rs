@@ -0,0 +1,359 @@
+/// Module that persists benchmark timings as named baselines and compares new runs against them.
+use std::{fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::report::DurationReport;
+use crate::template::stats::Stats;
+use crate::template::{get_data_path, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Critical value of a two-sided test at a 95% confidence level.
+/// Benchmarks collect at least 10 samples, so the normal approximation is good enough here.
+const SIGNIFICANCE_THRESHOLD: f64 = 1.96;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidName(String),
+    Json(serde_json::Error),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidName(name) => write!(f, "\"{name}\" is not a valid baseline name."),
+            Error::Json(e) => write!(f, "could not parse baseline: {e}"),
+            Error::IO(e) => write!(f, "could not access baseline file: {e}"),
+        }
+    }
+}
+
+/// The timing of one part of a solution, as stored in a baseline file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    samples: u128,
+    duration: DurationReport,
+}
+
+impl Entry {
+    fn stats(&self) -> Stats {
+        self.duration.to_stats(self.samples)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Baseline {
+    entries: Vec<Entry>,
+}
+
+/// The difference between a baseline and the current run for one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Stats,
+    pub current: Stats,
+    /// Relative change of the mean execution time, in percent.
+    pub delta_percent: f64,
+    /// Whether the change in mean execution time is statistically significant.
+    pub is_significant: bool,
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn is_regression(&self) -> bool {
+        self.is_significant && self.delta_percent > 0.0
+    }
+
+    #[must_use]
+    pub fn is_improvement(&self) -> bool {
+        self.is_significant && self.delta_percent < 0.0
+    }
+}
+
+fn get_path(year: Year, name: &str) -> Result<PathBuf, Error> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.');
+
+    if !is_valid {
+        return Err(Error::InvalidName(name.into()));
+    }
+
+    Ok(get_data_path("baselines", year, &format!("{name}.json")))
+}
+
+fn parts(timing: &Timings) -> impl Iterator<Item = (u8, &Stats)> {
+    [(1, timing.part_1.as_ref()), (2, timing.part_2.as_ref())]
+        .into_iter()
+        .filter_map(|(part, stats)| Some((part, stats?)))
+}
+
+fn to_baseline(timings: &[Timings]) -> Baseline {
+    let entries = timings
+        .iter()
+        .flat_map(|timing| {
+            parts(timing).map(|(part, stats)| Entry {
+                day: timing.day.into_inner(),
+                part,
+                samples: stats.samples,
+                duration: stats.into(),
+            })
+        })
+        .collect();
+
+    Baseline { entries }
+}
+
+fn from_baseline(baseline: &Baseline) -> Vec<Timings> {
+    let mut timings: Vec<Timings> = vec![];
+
+    for entry in &baseline.entries {
+        let Some(day) = Day::new(entry.day) else {
+            continue;
+        };
+
+        let stats = entry.stats();
+
+        let timing = match timings.iter_mut().find(|t| t.day == day) {
+            Some(timing) => timing,
+            None => {
+                timings.push(Timings {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: 0_f64,
+                });
+                timings.last_mut().unwrap()
+            }
+        };
+
+        match entry.part {
+            1 => timing.part_1 = Some(stats),
+            2 => timing.part_2 = Some(stats),
+            _ => continue,
+        }
+
+        timing.total_nanos += stats.mean.as_secs_f64() * 1_000_000_000_f64;
+    }
+
+    timings
+}
+
+/// Writes the timings to `data/<year>/baselines/<name>.json`, replacing an existing baseline of the same name.
+pub fn save(year: Year, name: &str, timings: &[Timings]) -> Result<PathBuf, Error> {
+    let path = get_path(year, name)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let json = serde_json::to_string_pretty(&to_baseline(timings))?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Reads the timings stored in `data/<year>/baselines/<name>.json`.
+pub fn load(year: Year, name: &str) -> Result<Vec<Timings>, Error> {
+    let path = get_path(year, name)?;
+    let baseline: Baseline = serde_json::from_str(&fs::read_to_string(path)?)?;
+    Ok(from_baseline(&baseline))
+}
+
+/// Welch's t-statistic for the difference of the mean execution times.
+/// Returns [`None`] if there is not enough data to tell.
+fn t_statistic(baseline: &Stats, current: &Stats) -> Option<f64> {
+    if baseline.samples < 2 || current.samples < 2 {
+        return None;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let variance = |stats: &Stats| stats.std_dev.as_secs_f64().powi(2) / stats.samples as f64;
+
+    let standard_error = (variance(baseline) + variance(current)).sqrt();
+    let delta = current.mean.as_secs_f64() - baseline.mean.as_secs_f64();
+
+    if standard_error == 0.0 {
+        return if delta == 0.0 { Some(0.0) } else { None };
+    }
+
+    Some(delta / standard_error)
+}
+
+/// Compares every part that is present in both the baseline and the current run.
+#[must_use]
+pub fn compare(baseline: &[Timings], current: &[Timings]) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for timing in current {
+        let Some(previous) = baseline.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for (part, current_stats) in parts(timing) {
+            let Some((_, baseline_stats)) = parts(previous).find(|(p, _)| *p == part) else {
+                continue;
+            };
+
+            let baseline_secs = baseline_stats.mean.as_secs_f64();
+
+            let delta_percent = if baseline_secs == 0.0 {
+                0.0
+            } else {
+                (current_stats.mean.as_secs_f64() - baseline_secs) / baseline_secs * 100.0
+            };
+
+            let is_significant = t_statistic(baseline_stats, current_stats)
+                .is_some_and(|t| t.abs() > SIGNIFICANCE_THRESHOLD);
+
+            comparisons.push(Comparison {
+                day: timing.day,
+                part,
+                baseline: *baseline_stats,
+                current: *current_stats,
+                delta_percent,
+                is_significant,
+            });
+        }
+    }
+
+    comparisons
+}
+
+/// Formats the comparison against the baseline `name` as a human-readable summary.
+#[must_use]
+pub fn format_comparison(name: &str, comparisons: &[Comparison]) -> String {
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}Comparison with baseline \"{name}\":{ANSI_RESET}"
+    )];
+
+    if comparisons.is_empty() {
+        lines.push("No common parts to compare.".into());
+    }
+
+    for c in comparisons {
+        let verdict = if c.is_regression() {
+            format!(" {ANSI_BOLD}▲ regression{ANSI_RESET}")
+        } else if c.is_improvement() {
+            " ▼ improvement".into()
+        } else {
+            String::new()
+        };
+
+        lines.push(format!(
+            "Day {} Part {}: {:.1?} → {:.1?} ({:+.1}%){verdict}",
+            c.day, c.part, c.baseline.mean, c.current.mean, c.delta_percent
+        ));
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.is_regression()).count();
+    if regressions > 0 {
+        lines.push(format!(
+            "{ANSI_BOLD}{regressions} significant regression(s) found.{ANSI_RESET}"
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, from_baseline, get_path, to_baseline, Timings};
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+
+    fn stats(mean_micros: u64, std_dev_micros: u64) -> Stats {
+        let mut stats = Stats::from_duration(Duration::from_micros(mean_micros));
+        stats.samples = 100;
+        stats.std_dev = Duration::from_micros(std_dev_micros);
+        stats
+    }
+
+    fn timings(part_1: Stats, part_2: Option<Stats>) -> Vec<Timings> {
+        vec![Timings {
+            day: day!(17),
+            part_1: Some(part_1),
+            part_2,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos: 0_f64,
+        }]
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(get_path(year!(2023), "main").is_ok());
+        assert!(get_path(year!(2023), "before-refactor_2").is_ok());
+        assert!(get_path(year!(2023), "").is_err());
+        assert!(get_path(year!(2023), "../README").is_err());
+        assert!(get_path(year!(2023), "a/b").is_err());
+    }
+
+    #[test]
+    fn baseline_roundtrip() {
+        let original = timings(stats(100, 5), Some(stats(200, 10)));
+        let restored = from_baseline(&to_baseline(&original));
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].day, day!(17));
+        assert_eq!(restored[0].part_1, original[0].part_1);
+        assert_eq!(restored[0].part_2, original[0].part_2);
+        assert!((restored[0].total_nanos - 300_000_f64).abs() < 1e-6);
+    }
+
+    #[test]
+    fn flags_significant_regression() {
+        let baseline = timings(stats(100, 5), None);
+        let current = timings(stats(150, 5), None);
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert!((comparisons[0].delta_percent - 50.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let baseline = timings(stats(100, 50), None);
+        let current = timings(stats(102, 50), None);
+        let comparisons = compare(&baseline, &current);
+        assert!(!comparisons[0].is_significant);
+        assert!(!comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn flags_improvement() {
+        let baseline = timings(stats(100, 5), None);
+        let current = timings(stats(50, 5), None);
+        let comparisons = compare(&baseline, &current);
+        assert!(comparisons[0].is_improvement());
+        assert!(!comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn skips_missing_parts() {
+        let baseline = timings(stats(100, 5), None);
+        let current = timings(stats(100, 5), Some(stats(100, 5)));
+        assert_eq!(compare(&baseline, &current).len(), 1);
+    }
+}
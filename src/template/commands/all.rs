@@ -1,254 +1,426 @@
-use std::io;
+use std::io::{stdout, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
 use crate::template::{
+    answers, baseline, get_data_path, memory,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::Registry,
+    report::{OutputFormat, PartReport, Report},
+    runner::{self, Failure, Measurement},
+    try_read_file, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, PuzzleId, Year};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+/// The result of running one part of a solution.
+struct PartResult {
+    part: u8,
+    measurement: Measurement,
+    correct: Option<bool>,
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+enum DayOutcome {
+    NotSolved,
+    NoInput,
+    Ran(Vec<PartResult>),
+}
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    registry: &Registry,
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    format: OutputFormat,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+    mem: bool,
+    save_baseline: Option<String>,
+    compare_baseline: Option<String>,
+) {
+    if mem {
+        memory::enable();
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // solutions run in-process, so `--release` cannot switch to an optimized build by itself.
+    if is_release && cfg!(debug_assertions) {
+        eprintln!(
+            "Warning: `--release` has no effect on a debug build, run `cargo all --release`."
+        );
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
-        }
-    });
+    let outcomes = match jobs {
+        Some(jobs) => run_parallel(registry, year, is_timed, timeout, jobs),
+        None => run_sequential(registry, year, is_timed, timeout, format),
+    };
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    if jobs.is_some() && format == OutputFormat::Text {
+        print_summary(&outcomes);
+    }
+
+    let mut timings: Vec<Timings> = vec![];
+    let mut reports: Vec<PartReport> = vec![];
+
+    for (day, outcome) in &outcomes {
+        if let DayOutcome::Ran(parts) = outcome {
+            timings.push(to_timings(*day, parts));
+            reports.extend(parts.iter().map(|p| {
+                let id = PuzzleId::new(year, *day);
+                p.measurement.to_report(id, p.part, p.correct)
+            }));
+        }
+    }
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    let total_nanos = timings.iter().map(|x| x.total_nanos).sum::<f64>();
+    let total_millis = total_nanos / 1_000_000_f64;
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+    match format {
+        OutputFormat::Text if is_timed => {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        OutputFormat::Text => {}
+        OutputFormat::Json => {
+            let report = Report {
+                parts: reports,
+                total_nanos,
+            };
+
+            match serde_json::to_string_pretty(&report) {
+                Ok(json) => println!("{json}"),
+                Err(e) => eprintln!("Failed to serialize results: {e}"),
             }
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
+    if is_timed {
+        handle_baselines(
+            year,
+            &timings,
+            save_baseline.as_deref(),
+            compare_baseline.as_deref(),
+            format,
+        );
+    } else if save_baseline.is_some() || compare_baseline.is_some() {
+        eprintln!("Baselines require benchmark timings, re-run with `--time`.");
+    }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+    // only optimized builds produce timings worth publishing, the readme lists the default year.
+    if is_timed && is_release && !cfg!(debug_assertions) && year == Year::default() {
+        match readme_benchmarks::update(year, timings, total_millis) {
+            // keep stdout parseable when emitting JSON.
+            Ok(()) if format == OutputFormat::Json => {
+                eprintln!("Successfully updated README with benchmarks.");
+            }
+            Ok(()) => println!("Successfully updated README with benchmarks."),
+            Err(_) => {
+                eprintln!("Failed to update readme with benchmarks.");
+            }
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Runs all days one after another, printing the results of each day as soon as they are available.
+fn run_sequential(
+    registry: &Registry,
+    year: Year,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    format: OutputFormat,
+) -> Vec<(Day, DayOutcome)> {
+    let is_live = format == OutputFormat::Text;
+
+    all_days()
+        .map(|day| {
+            if is_live {
+                if day > 1 {
+                    println!();
+                }
+
+                println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+                println!("------");
+            }
+
+            let id = PuzzleId::new(year, day);
+            let outcome = run_day(registry, id, is_timed, timeout, is_live, None);
+
+            if is_live && matches!(outcome, DayOutcome::NotSolved) {
+                println!("Not solved.");
+            }
+
+            (day, outcome)
+        })
+        .collect()
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Runs days on `jobs` worker threads. Benchmarks are serialized so that timed runs do not compete for the CPU.
+fn run_parallel(
+    registry: &Registry,
+    year: Year,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Vec<(Day, DayOutcome)> {
+    let days: Vec<Day> = all_days().collect();
+    let next = AtomicUsize::new(0);
+    let bench_lock = Mutex::new(());
+    let outcomes = Mutex::new(vec![]);
+
+    let jobs = if jobs == 0 {
+        thread::available_parallelism().map_or(1, usize::from)
+    } else {
+        jobs
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            scope.spawn(|| {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let id = PuzzleId::new(year, day);
+                    let outcome =
+                        run_day(registry, id, is_timed, timeout, false, Some(&bench_lock));
+                    outcomes.lock().unwrap().push((day, outcome));
+                }
+            });
         }
+    });
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|(day, _)| *day);
+    outcomes
+}
 
-        if is_release {
-            args.push("--release");
-        }
+/// Runs both parts of the solution for a puzzle in-process.
+/// If `is_live` is set, progress and results are printed while running.
+/// If a `bench_lock` is passed, it is held while the parts of a timed run execute.
+/// Parts that run longer than `timeout` are abandoned.
+fn run_day(
+    registry: &Registry,
+    id: PuzzleId,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    is_live: bool,
+    bench_lock: Option<&Mutex<()>>,
+) -> DayOutcome {
+    let Some(solution) = registry.get(id) else {
+        return DayOutcome::NotSolved;
+    };
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+    let Ok(input) = try_read_file("inputs", id) else {
+        eprintln!(
+            "Could not read input file \"{}\".",
+            get_data_path("inputs", id.year, &format!("{}.txt", id.day)).display()
+        );
+        return DayOutcome::NoInput;
+    };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    let known_answers = answers::read(id);
+    let _guard = bench_lock
+        .filter(|_| is_timed)
+        .map(|lock| lock.lock().unwrap());
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    let mut parts = vec![];
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    for part in 1..=2 {
+        if is_live && is_timed {
+            print!("Part {part}: > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
 
-        let mut output = vec![];
+        let Some(measurement) = solution.run_part(part, &input, is_timed, timeout) else {
+            continue;
+        };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let correct = answers::check(&known_answers, part, measurement.answer.as_deref());
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if is_live {
+            runner::print_measurement(part, &measurement, correct);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        parts.push(PartResult {
+            part,
+            measurement,
+            correct,
+        });
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    DayOutcome::Ran(parts)
+}
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+fn to_timings(day: Day, parts: &[PartResult]) -> Timings {
+    let mut timings = Timings {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_memory: None,
+        part_2_memory: None,
+        total_nanos: 0_f64,
+    };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    // unsolved parts do not contribute to the benchmarks.
+    for result in parts.iter().filter(|p| p.measurement.answer.is_some()) {
+        let stats = result.measurement.stats;
 
-                timings.total_nanos += nanos;
-            });
+        if result.part == 1 {
+            timings.part_1 = Some(stats);
+            timings.part_1_memory = result.measurement.memory;
+        } else {
+            timings.part_2 = Some(stats);
+            timings.part_2_memory = result.measurement.memory;
+        }
 
-        timings
+        timings.total_nanos += stats.mean.as_secs_f64() * 1_000_000_000_f64;
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    timings
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+fn format_answer(result: Option<&PartResult>) -> String {
+    let Some(result) = result else {
+        return "-".into();
+    };
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    let verdict = match result.correct {
+        Some(true) => " ✔",
+        Some(false) => " ✘",
+        None => "",
+    };
+
+    match (&result.measurement.failure, &result.measurement.answer) {
+        (Some(Failure::Timeout(_)), _) => "timeout".into(),
+        (Some(Failure::Panicked(_)), _) => "panicked".into(),
+        (Some(Failure::Error(_)), _) => "error".into(),
+        (None, Some(answer)) if answer.contains('\n') => format!("▼{verdict}"),
+        (None, Some(answer)) => format!("{answer}{verdict}"),
+        (None, None) => "✖".into(),
     }
+}
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+fn format_status(outcome: &DayOutcome) -> &'static str {
+    match outcome {
+        DayOutcome::NotSolved => "not solved",
+        DayOutcome::NoInput => "no input",
+        DayOutcome::Ran(parts) => {
+            if parts.iter().any(|p| p.correct == Some(false)) {
+                "wrong"
+            } else if parts
+                .iter()
+                .any(|p| matches!(p.measurement.failure, Some(Failure::Panicked(_))))
+            {
+                "panicked"
+            } else if parts
+                .iter()
+                .any(|p| matches!(p.measurement.failure, Some(Failure::Error(_))))
+            {
+                "error"
+            } else if parts
+                .iter()
+                .any(|p| matches!(p.measurement.failure, Some(Failure::Timeout(_))))
+            {
+                "timeout"
+            } else if parts.iter().all(|p| p.measurement.answer.is_some()) {
+                "solved"
+            } else if parts.iter().any(|p| p.measurement.answer.is_some()) {
+                "partial"
+            } else {
+                "unsolved"
+            }
         }
+    }
+}
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+/// Prints one table row per day with the answers, status and total time of both parts.
+fn print_summary(outcomes: &[(Day, DayOutcome)]) {
+    let rows: Vec<[String; 5]> = outcomes
+        .iter()
+        .map(|(day, outcome)| {
+            let parts: &[PartResult] = match outcome {
+                DayOutcome::Ran(parts) => parts,
+                _ => &[],
+            };
+
+            let time = if parts.is_empty() {
+                "-".into()
+            } else {
+                format!(
+                    "{:.1?}",
+                    parts
+                        .iter()
+                        .map(|p| p.measurement.stats.mean)
+                        .sum::<std::time::Duration>()
+                )
+            };
+
+            [
+                day.to_string(),
+                format_answer(parts.iter().find(|p| p.part == 1)),
+                format_answer(parts.iter().find(|p| p.part == 2)),
+                format_status(outcome).into(),
+                time,
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Part 1", "Part 2", "Status", "Time"].map(String::from);
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[String; 5]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header));
+
+    for row in &rows {
+        println!("{}", format_row(row));
+    }
+}
+
+fn handle_baselines(
+    year: Year,
+    timings: &[Timings],
+    save_name: Option<&str>,
+    compare_name: Option<&str>,
+    format: OutputFormat,
+) {
+    if let Some(name) = compare_name {
+        match baseline::load(year, name) {
+            Ok(previous) => {
+                let comparison =
+                    baseline::format_comparison(name, &baseline::compare(&previous, timings));
+
+                // keep stdout parseable when emitting JSON.
+                if format == OutputFormat::Json {
+                    eprintln!("{comparison}");
+                } else {
+                    println!("\n{comparison}");
+                }
+            }
+            Err(e) => eprintln!("Failed to load baseline \"{name}\": {e}"),
         }
+    }
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+    if let Some(name) = save_name {
+        match baseline::save(year, name, timings) {
+            Ok(path) => eprintln!("Saved baseline \"{name}\" to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to save baseline \"{name}\": {e}"),
         }
     }
 }
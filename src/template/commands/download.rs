@@ -1,15 +1,20 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client;
+use crate::template::extract::{self, Pick};
+use crate::PuzzleId;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(id: PuzzleId, pick: Pick) {
+    if let Err(e) = aoc_client::download(id) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
-    }
+    };
+
+    let Some(analysis) = extract::read_analysis(id) else {
+        return;
+    };
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = extract::write_examples(id, &analysis, pick) {
+        eprintln!("failed to extract examples: {e}");
         process::exit(1);
     };
 }
@@ -3,3 +3,5 @@ pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod verify;
+pub mod watch;
@@ -1,51 +1,175 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use regex::{Captures, Regex};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+use crate::template::answers::Answers;
+use crate::template::extract::{self, Pick};
+use crate::template::{examples, get_data_path};
+use crate::{Day, PuzzleId, Year};
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+/// The template used if the project has no `templates/default.rs`.
+const DEFAULT_TEMPLATE: &str = include_str!("../../../templates/default.rs");
+
+const TEMPLATE_DIR: &str = "templates";
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    Some(name.strip_suffix(".rs")?.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+    names.sort();
+    names
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Reads `templates/<name>.rs`. Without a name, the default template is used.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{}.rs", name.unwrap_or("default")));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name.is_none() => Ok(DEFAULT_TEMPLATE.into()),
+        Err(e) => Err(format!(
+            "could not read template \"{}\": {e}. Available templates: {}.",
+            path.display(),
+            available_templates().join(", ")
+        )),
     }
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// Picks the narrowest type that fits all known answers, defaulting to `u32`.
+fn answer_type(answers: &Answers) -> &'static str {
+    const TYPES: [&str; 4] = ["u32", "u64", "i64", "String"];
+
+    let rank = |answer: &str| {
+        if answer.parse::<u32>().is_ok() {
+            0
+        } else if answer.parse::<u64>().is_ok() {
+            1
+        } else if answer.parse::<i64>().is_ok() {
+            2
+        } else {
+            3
+        }
+    };
+
+    let max_rank = [answers.get(1), answers.get(2)]
+        .into_iter()
+        .flatten()
+        .map(rank)
+        .max()
+        .unwrap_or(0);
+
+    TYPES[max_rank]
+}
+
+/// The answer of `part` as the `Option<&str>` expected by the scaffolded tests.
+fn answer_literal(answers: &Answers, part: u8) -> String {
+    answers
+        .get(part)
+        .map_or_else(|| "None".into(), |answer| format!("Some({answer:?})"))
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Replaces all `{{name}}` placeholders of a template. Returns the names of unknown placeholders as well.
+fn render(template: &str, placeholders: &[(&str, String)]) -> (String, Vec<String>) {
+    let re = Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap();
+    let mut unknown = vec![];
+
+    let rendered = re.replace_all(template, |captures: &Captures| {
+        match placeholders.iter().find(|(name, _)| *name == &captures[1]) {
+            Some((_, value)) => value.clone(),
+            None => {
+                unknown.push(captures[1].to_string());
+                captures[0].to_string()
+            }
+        }
+    });
+
+    (rendered.to_string(), unknown)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffolds a solution for `day` from `templates/<template>.rs`. If a `year` is passed, the
+/// solution is created as `src/bin/<year>-<day>.rs` so it can coexist with the solutions of other years.
+/// If the puzzle description was downloaded already, its examples are extracted as well.
+pub fn handle(day: Day, year: Option<Year>, template: Option<&str>, pick: Pick) {
+    let id = PuzzleId::new(year.unwrap_or_default(), day);
+    let input_path = get_data_path("inputs", id.year, &format!("{day}.txt"));
+    let example_path = get_data_path("examples", id.year, &format!("{day}.txt"));
+
+    let (module_path, solution_args) = match year {
+        Some(year) => (
+            Path::new("src").join("bin").join(format!("{id}.rs")),
+            format!("{year}, {}", day.into_inner()),
+        ),
+        None => (
+            Path::new("src").join("bin").join(format!("{day}.rs")),
+            day.into_inner().to_string(),
+        ),
+    };
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let analysis = extract::read_analysis(id);
+
+    let title = analysis
+        .as_ref()
+        .and_then(|a| a.title.clone())
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    let answers = analysis
+        .as_ref()
+        .map(|a| a.answers.clone())
+        .unwrap_or_default();
+
+    let (module, unknown) = render(
+        &template,
+        &[
+            ("day", day.into_inner().to_string()),
+            ("year", id.year.to_string()),
+            ("solution_args", solution_args),
+            ("title", title),
+            ("answer_type", answer_type(&answers).into()),
+            ("part_one_answer", answer_literal(&answers, 1)),
+            ("part_two_answer", answer_literal(&answers, 2)),
+        ],
+    );
+
+    if !unknown.is_empty() {
+        eprintln!("Warning: unknown placeholder(s) in template: {unknown:?}.");
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,13 +179,9 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -71,7 +191,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -79,16 +199,97 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match &analysis {
+        Some(analysis) => {
+            if let Err(e) = extract::write_examples(id, analysis, pick) {
+                eprintln!("Failed to extract examples: {e}");
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        None => println!("No puzzle description found, skipping example extraction."),
+    }
+
+    if examples::all(id).is_empty() {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", example_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_literal, answer_type, render, DEFAULT_TEMPLATE};
+    use crate::template::answers::Answers;
+
+    fn answers(part_1: &str, part_2: Option<&str>) -> Answers {
+        Answers {
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let (rendered, unknown) = render(
+            "//! {{title}}\nadvent_of_code::solution!({{ solution_args }}); {{day}} {{other}}",
+            &[
+                ("title", "Day 1: Trebuchet?!".into()),
+                ("solution_args", "2022, 1".into()),
+                ("day", "1".into()),
+            ],
+        );
+
+        assert_eq!(
+            rendered,
+            "//! Day 1: Trebuchet?!\nadvent_of_code::solution!(2022, 1); 1 {{other}}"
+        );
+        assert_eq!(unknown, vec!["other".to_string()]);
+    }
+
+    #[test]
+    fn renders_default_template() {
+        let (rendered, unknown) = render(
+            DEFAULT_TEMPLATE,
+            &[
+                ("solution_args", "5".into()),
+                ("answer_type", "u64".into()),
+                ("part_one_answer", "Some(\"35\")".into()),
+                ("part_two_answer", "None".into()),
+            ],
+        );
+
+        assert!(unknown.is_empty());
+        assert!(rendered.starts_with("advent_of_code::solution!(5);"));
+        assert!(rendered.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(rendered.contains("const PART_ONE: Option<&str> = Some(\"35\");"));
+        assert!(rendered.contains("const PART_TWO: Option<&str> = None;"));
+    }
+
+    #[test]
+    fn renders_answers() {
+        let answers = answers("ABC \"D\"", None);
+        assert_eq!(answer_literal(&answers, 1), r#"Some("ABC \"D\"")"#);
+        assert_eq!(answer_literal(&answers, 2), "None");
+    }
+
+    #[test]
+    fn picks_answer_type() {
+        assert_eq!(answer_type(&Answers::default()), "u32");
+        assert_eq!(answer_type(&answers("142", Some("281"))), "u32");
+        assert_eq!(answer_type(&answers("142", Some("5000000000"))), "u64");
+        assert_eq!(answer_type(&answers("-3", None)), "i64");
+        assert_eq!(answer_type(&answers("ABCD", Some("1"))), "String");
+    }
 }
@@ -1,9 +1,36 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::Day;
+use crate::template::get_bin_path;
+use crate::template::input::InputSource;
+use crate::template::report::OutputFormat;
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Returns the name of the solution binary of a puzzle. Exits if there is no solution.
+pub(crate) fn get_bin_name(id: PuzzleId) -> String {
+    let bin_path = get_bin_path(id);
+
+    if !bin_path.exists() {
+        eprintln!("No solution found for day {} of {}.", id.day, id.year);
+        process::exit(1);
+    }
+
+    bin_path.file_stem().unwrap().to_string_lossy().to_string()
+}
+
+/// Builds the arguments of the `cargo run` invocation for a solution binary.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn get_run_args(
+    bin: &str,
+    release: bool,
+    time: bool,
+    format: OutputFormat,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    mem: bool,
+    input: &InputSource,
+) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin.to_string()];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,6 +47,48 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if mem {
+        cmd_args.push("--mem".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    if format == OutputFormat::Json {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.as_secs_f64().to_string());
+    }
+
+    cmd_args
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    time: bool,
+    format: OutputFormat,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    mem: bool,
+    input: &InputSource,
+) {
+    let bin = get_bin_name(id);
+    let cmd_args = get_run_args(
+        &bin,
+        release,
+        time,
+        format,
+        submit_part,
+        timeout,
+        mem,
+        input,
+    );
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
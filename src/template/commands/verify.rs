@@ -0,0 +1,59 @@
+use std::process;
+
+use crate::template::answers::{self, Answers};
+use crate::template::registry::Registry;
+use crate::template::{try_read_file, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, PuzzleId, Year};
+
+/// Runs every day of `year` that has known answers in `data/<year>/answers/` and compares the results.
+/// Exits with a non-zero status if any result does not match.
+pub fn handle(registry: &Registry, year: Year) {
+    let mut checked = 0;
+    let mut mismatches = 0;
+
+    for day in all_days() {
+        let id = PuzzleId::new(year, day);
+        let expected = answers::read(id);
+
+        if expected == Answers::default() {
+            continue;
+        }
+
+        let solution = registry.get(id);
+        let input = try_read_file("inputs", id).ok();
+
+        for part in 1..=2 {
+            let Some(expected_answer) = expected.get(part) else {
+                continue;
+            };
+
+            let answer = match (solution, &input) {
+                (Some(solution), Some(input)) => solution
+                    .run_part(part, input, false, None)
+                    .and_then(|m| m.answer),
+                _ => None,
+            };
+
+            checked += 1;
+
+            if answers::check(&expected, part, answer.as_deref()) == Some(true) {
+                println!("Day {day} Part {part}: ✔ {expected_answer}");
+            } else {
+                mismatches += 1;
+                println!(
+                    "Day {day} Part {part}: ✘ got {}, expected {expected_answer}",
+                    answer.as_deref().unwrap_or("nothing")
+                );
+            }
+        }
+    }
+
+    println!("---");
+
+    if mismatches > 0 {
+        println!("{ANSI_BOLD}{mismatches} of {checked} answers did not match.{ANSI_RESET}");
+        process::exit(1);
+    }
+
+    println!("🎄 All {checked} known answers match.");
+}
@@ -0,0 +1,264 @@
+/// Module that re-runs a solution whenever its sources or data files change (`cargo solve <day> --watch`).
+/// Files are polled for changes, so this works without platform-specific file system events.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::commands::solve::{get_bin_name, get_run_args};
+use crate::template::input::InputSource;
+use crate::template::report::{OutputFormat, PartReport, Status};
+use crate::template::runner::{print_measurement, Failure, Measurement};
+use crate::template::{get_bin_path, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, PuzzleId};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often write a file in several steps, wait for them to finish before re-running.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Whether a data file belongs to `day`, e.g. `05.txt`, `05-1b.txt` or `05.answers`.
+fn is_day_file(day: Day, name: &str) -> bool {
+    name.strip_prefix(&day.to_string())
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'))
+}
+
+fn collect_files(dir: &Path, files: &mut Snapshot, filter: &dyn Fn(&str) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(&path, files, filter);
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(filter)
+        {
+            if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                files.insert(path, modified);
+            }
+        }
+    }
+}
+
+/// Collects the modification times of the solution, the template code and the data files of the day.
+fn snapshot(id: PuzzleId) -> Snapshot {
+    let mut files = Snapshot::new();
+
+    let bin_path = get_bin_path(id);
+    if let Ok(modified) = fs::metadata(&bin_path).and_then(|m| m.modified()) {
+        files.insert(bin_path, modified);
+    }
+
+    collect_files(&Path::new("src").join("template"), &mut files, &|_| true);
+
+    for data_dir in [
+        PathBuf::from("data").join(id.year.to_string()),
+        "data".into(),
+    ] {
+        for folder in ["inputs", "examples"] {
+            collect_files(&data_dir.join(folder), &mut files, &|name| {
+                is_day_file(id.day, name)
+            });
+        }
+    }
+
+    files
+}
+
+/// Describes how the answer of a part changed since the previous run.
+fn describe_change(previous: &[PartReport], current: &PartReport) -> Option<String> {
+    let previous = previous.iter().find(|p| p.part == current.part)?;
+
+    if previous.answer == current.answer {
+        return None;
+    }
+
+    Some(format!(
+        "{ANSI_ITALIC}changed from {}{ANSI_RESET}",
+        previous.answer.as_deref().unwrap_or("✖")
+    ))
+}
+
+/// Runs the solution once and prints the answers of both parts.
+/// Returns the reports of the run, or [`None`] if it failed.
+fn run_solution(
+    bin: &str,
+    release: bool,
+    time: bool,
+    timeout: Option<Duration>,
+    mem: bool,
+    input: &InputSource,
+    previous: &[PartReport],
+) -> Option<Vec<PartReport>> {
+    let output = Command::new("cargo")
+        .args(get_run_args(
+            bin,
+            release,
+            time,
+            OutputFormat::Json,
+            None,
+            timeout,
+            mem,
+            input,
+        ))
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    let mut reports = vec![];
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match serde_json::from_str::<PartReport>(line) {
+            Ok(report) => reports.push(report),
+            // pass through debug output of the solution.
+            Err(_) => println!("{line}"),
+        }
+    }
+
+    if !output.status.success() {
+        return None;
+    }
+
+    for report in &reports {
+        let stats = report.stats();
+        let failure = match report.status {
+            Status::Timeout => Some(Failure::Timeout(stats.mean)),
+            Status::Panicked => Some(Failure::Panicked(report.error.clone().unwrap_or_default())),
+            Status::Error => Some(Failure::Error(report.error.clone().unwrap_or_default())),
+            _ => None,
+        };
+        let measurement = Measurement {
+            answer: report.answer.clone(),
+            stats,
+            failure,
+            memory: report.memory,
+        };
+
+        print_measurement(report.part, &measurement, report.correct);
+
+        if let Some(change) = describe_change(previous, report) {
+            println!("  {change}");
+        }
+    }
+
+    Some(reports)
+}
+
+fn run_tests(bin: &str) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", bin])
+        .stdin(Stdio::null())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("Tests passed."),
+        Ok(_) => println!("{ANSI_BOLD}Tests failed.{ANSI_RESET}"),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+}
+
+/// Runs the solution of a puzzle (and its tests if `test` is set) on every change until interrupted.
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    time: bool,
+    timeout: Option<Duration>,
+    mem: bool,
+    input: &InputSource,
+    test: bool,
+) {
+    let bin = get_bin_name(id);
+    let mut files = snapshot(id);
+    let mut previous = vec![];
+
+    loop {
+        println!("{ANSI_BOLD}Day {} ({}){ANSI_RESET}", id.day, id.year);
+        println!("------");
+
+        match run_solution(&bin, release, time, timeout, mem, input, &previous) {
+            Some(reports) => previous = reports,
+            None => println!("{ANSI_BOLD}Run failed.{ANSI_RESET}"),
+        }
+
+        if test {
+            run_tests(&bin);
+        }
+
+        println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            if snapshot(id) != files {
+                thread::sleep(SETTLE_DELAY);
+                files = snapshot(id);
+                break;
+            }
+        }
+
+        println!();
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_change, is_day_file};
+    use crate::day;
+    use crate::template::report::{DurationReport, PartReport, Status};
+
+    fn report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport {
+            year: 2023,
+            day: 5,
+            part,
+            answer: answer.map(Into::into),
+            correct: None,
+            status: Status::Solved,
+            error: None,
+            samples: 1,
+            memory: None,
+            duration: DurationReport {
+                mean_ns: 0,
+                median_ns: 0,
+                min_ns: 0,
+                max_ns: 0,
+                std_dev_ns: 0,
+                p95_ns: 0,
+                p99_ns: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn matches_day_files() {
+        assert!(is_day_file(day!(5), "05.txt"));
+        assert!(is_day_file(day!(5), "05-1b.txt"));
+        assert!(is_day_file(day!(5), "05.answers"));
+        assert!(!is_day_file(day!(5), "15.txt"));
+        assert!(!is_day_file(day!(5), "050.txt"));
+    }
+
+    #[test]
+    fn describes_changed_answers() {
+        let previous = vec![report(1, Some("41")), report(2, None)];
+
+        assert!(describe_change(&previous, &report(1, Some("41"))).is_none());
+        assert!(describe_change(&[], &report(1, Some("42"))).is_none());
+        assert!(describe_change(&previous, &report(1, Some("42")))
+            .unwrap()
+            .contains("changed from 41"));
+        assert!(describe_change(&previous, &report(2, Some("7")))
+            .unwrap()
+            .contains("changed from ✖"));
+    }
+}
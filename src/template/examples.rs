@@ -0,0 +1,180 @@
+/// Module that reads the example inputs of a day from `data/<year>/examples/`.
+///
+/// Besides the default `<day>.txt`, a day can have any number of additional examples named
+/// `<day>-<part><variant>.txt`: `08-1.txt` is only used for part 1, `08-1b.txt` is a second
+/// example for part 1 and `08-b.txt` is a second example for both parts.
+/// The expected answers of an example are stored next to it in `<name>.answers`, using the same
+/// `Part 1: 42` format as the known answers in `data/<year>/answers/`.
+use std::fs;
+
+use crate::template::answers::{self, Answers};
+use crate::template::resolve_data_dir;
+use crate::{Day, PuzzleId};
+
+/// One example input together with its expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// File name of the example without extension, e.g. `08-1b`.
+    pub name: String,
+    /// The part this example is restricted to, if any.
+    pub part: Option<u8>,
+    /// The variant of the example, empty for the first one.
+    pub variant: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+impl Example {
+    /// The expected answer for `part`, if one is stored next to the example.
+    #[must_use]
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers.get(part)
+    }
+
+    fn applies_to(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Splits an example name into its part and variant.
+/// Returns [`None`] if the name does not belong to an example of `day`.
+fn parse_name(day: Day, name: &str) -> Option<(Option<u8>, String)> {
+    let day = day.to_string();
+
+    if name == day {
+        return Some((None, String::new()));
+    }
+
+    let suffix = name.strip_prefix(&day)?.strip_prefix('-')?;
+
+    let (part, variant) = match suffix.chars().next()? {
+        c @ ('1' | '2') => (Some(c as u8 - b'0'), &suffix[1..]),
+        _ => (None, suffix),
+    };
+
+    let is_valid = variant
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+    is_valid.then(|| (part, variant.to_string()))
+}
+
+/// Reads all examples of a puzzle, sorted by name.
+/// `id` is either a [`Day`] of the default year or a [`PuzzleId`].
+#[must_use]
+pub fn all(id: impl Into<PuzzleId>) -> Vec<Example> {
+    let id = id.into();
+
+    let Ok(entries) = resolve_data_dir("examples", id.year).and_then(fs::read_dir) else {
+        return vec![];
+    };
+
+    let mut examples: Vec<Example> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path
+                .file_name()?
+                .to_str()?
+                .strip_suffix(".txt")?
+                .to_string();
+            let (part, variant) = parse_name(id.day, &name)?;
+
+            let input = fs::read_to_string(&path).ok()?;
+            let answers = fs::read_to_string(path.with_file_name(format!("{name}.answers")))
+                .map(|s| answers::parse(&s))
+                .unwrap_or_default();
+
+            Some(Example {
+                name,
+                part,
+                variant,
+                input,
+                answers,
+            })
+        })
+        .collect();
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    examples
+}
+
+/// Reads all examples that apply to `part`, sorted by name.
+#[must_use]
+pub fn for_part(id: impl Into<PuzzleId>, part: u8) -> Vec<Example> {
+    all(id).into_iter().filter(|e| e.applies_to(part)).collect()
+}
+
+/// Picks the example of `variant` for `part`, preferring examples that are specific to the part.
+fn select(examples: Vec<Example>, part: u8, variant: &str) -> Option<Example> {
+    examples
+        .into_iter()
+        .filter(|e| e.applies_to(part) && e.variant == variant)
+        .max_by_key(|e| e.part.is_some())
+}
+
+/// Finds the example `variant` for `part`, e.g. `get(DAY, 1, None)` finds `01-1.txt` if it exists
+/// and `01.txt` otherwise.
+#[must_use]
+pub fn get(id: impl Into<PuzzleId>, part: u8, variant: Option<&str>) -> Option<Example> {
+    select(all(id), part, variant.unwrap_or_default())
+}
+
+/// Reads the input of the example `variant` for `part`, e.g. `read(DAY, 1, None)` reads
+/// `01-1.txt` if it exists and `01.txt` otherwise.
+///
+/// # Panics
+/// Panics if there is no such example.
+#[must_use]
+pub fn read(id: impl Into<PuzzleId>, part: u8, variant: Option<&str>) -> String {
+    let id = id.into();
+    let variant = variant.unwrap_or_default();
+
+    get(id, part, Some(variant))
+        .map(|e| e.input)
+        .unwrap_or_else(|| {
+            panic!(
+                "could not find example \"{variant}\" for part {part} of day {}",
+                id.day
+            )
+        })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_name, select, Example};
+    use crate::day;
+    use crate::template::answers::Answers;
+
+    fn example(name: &str) -> Example {
+        let (part, variant) = parse_name(day!(8), name).unwrap();
+        Example {
+            name: name.into(),
+            part,
+            variant,
+            input: String::new(),
+            answers: Answers::default(),
+        }
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(parse_name(day!(8), "08"), Some((None, String::new())));
+        assert_eq!(parse_name(day!(8), "08-1"), Some((Some(1), String::new())));
+        assert_eq!(parse_name(day!(8), "08-2b"), Some((Some(2), "b".into())));
+        assert_eq!(parse_name(day!(8), "08-b"), Some((None, "b".into())));
+        assert_eq!(parse_name(day!(8), "08-"), None);
+        assert_eq!(parse_name(day!(8), "08-B"), None);
+        assert_eq!(parse_name(day!(8), "18"), None);
+        assert_eq!(parse_name(day!(1), "day01_part_a"), None);
+    }
+
+    #[test]
+    fn selects_part_specific_examples() {
+        let examples = || vec![example("08"), example("08-2"), example("08-b")];
+
+        assert_eq!(select(examples(), 1, "").unwrap().name, "08");
+        assert_eq!(select(examples(), 2, "").unwrap().name, "08-2");
+        assert_eq!(select(examples(), 2, "b").unwrap().name, "08-b");
+        assert!(select(examples(), 1, "c").is_none());
+    }
+}
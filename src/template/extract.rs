@@ -0,0 +1,314 @@
+/// Module that extracts example inputs and their answers from downloaded puzzle descriptions.
+///
+/// Examples are the `<pre><code>` blocks of a puzzle, the answer of a part is the last highlighted
+/// `<code><em>` value of its article. The first block of each part is used unless another one is
+/// picked, the second part reuses the example of the first part if its article has no blocks.
+/// If both parts have their own example, they are written as `<day>-1.txt` and `<day>-2.txt`.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{fs, io};
+
+use regex::Regex;
+
+use crate::template::answers::{self, Answers};
+use crate::template::aoc_client::{decode_entities, extract_articles};
+use crate::template::{get_data_path, resolve_data_path};
+use crate::{Day, PuzzleId};
+
+/// A `<pre><code>` block of a puzzle description that might be an example input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// Position of the block in the puzzle description, starting at 1.
+    pub number: usize,
+    /// The part whose article contains the block.
+    pub part: u8,
+    pub text: String,
+}
+
+/// Everything found in a puzzle description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// The heading of the puzzle without dashes, e.g. `Day 1: Trebuchet?!`.
+    pub title: Option<String>,
+    pub candidates: Vec<Candidate>,
+    pub answers: Answers,
+}
+
+/// The candidate blocks chosen via `--pick <part 1>[,<part 2>]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pick {
+    pub part_1: Option<usize>,
+    pub part_2: Option<usize>,
+}
+
+impl FromStr for Pick {
+    type Err = PickFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = s.split(',').map(|x| match x.trim().parse() {
+            Ok(0) | Err(_) => Err(PickFromStrError),
+            Ok(n) => Ok(n),
+        });
+
+        let pick = Self {
+            part_1: Some(numbers.next().ok_or(PickFromStrError)??),
+            part_2: numbers.next().transpose()?,
+        };
+
+        if numbers.next().is_some() {
+            return Err(PickFromStrError);
+        }
+
+        Ok(pick)
+    }
+}
+
+/// An error which can be returned when parsing a [`Pick`].
+#[derive(Debug)]
+pub struct PickFromStrError;
+
+impl Error for PickFromStrError {}
+
+impl Display for PickFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one or two block numbers, e.g. \"2\" or \"2,4\"")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the candidate blocks and answers in the HTML of a puzzle description.
+#[must_use]
+pub fn analyze(html: &str) -> Analysis {
+    let block_re = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer_re = Regex::new(r"<code><em>([^<]*)</em></code>").unwrap();
+    let tags_re = Regex::new(r"<[^>]+>").unwrap();
+    let title_re = Regex::new(r"<h2[^>]*>---\s*(.*?)\s*---</h2>").unwrap();
+
+    let mut analysis = Analysis {
+        title: title_re
+            .captures(html)
+            .map(|captures| decode_entities(&captures[1])),
+        ..Analysis::default()
+    };
+
+    for (part, article) in (1..=2).zip(extract_articles(html)) {
+        for captures in block_re.captures_iter(article) {
+            analysis.candidates.push(Candidate {
+                number: analysis.candidates.len() + 1,
+                part,
+                text: decode_entities(&tags_re.replace_all(&captures[1], "")),
+            });
+        }
+
+        if let Some(captures) = answer_re.captures_iter(article).last() {
+            analysis.answers.set(part, &decode_entities(&captures[1]));
+        }
+    }
+
+    analysis
+}
+
+/// Chooses the example of a part: the picked block if there is one, the first block of the part otherwise.
+fn choose(analysis: &Analysis, part: u8, pick: Option<usize>) -> Option<&Candidate> {
+    match pick {
+        Some(number) => analysis.candidates.iter().find(|c| c.number == number),
+        None => analysis.candidates.iter().find(|c| c.part == part),
+    }
+}
+
+/// The first line of a block, shortened to fit on one line.
+fn preview(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+
+    if line.chars().count() > 40 {
+        format!("{}…", line.chars().take(40).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// The name of the example of `part`. Examples are only restricted to their part if both parts
+/// have one of their own, otherwise the second part shares the example of the first.
+fn example_name(day: Day, part: u8, separate: bool) -> String {
+    if separate {
+        format!("{day}-{part}")
+    } else {
+        day.to_string()
+    }
+}
+
+/// Writes `text` to `path` unless the file already has content that should be kept.
+fn write_example(path: &PathBuf, text: &str, overwrite: bool) -> io::Result<bool> {
+    let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+
+    if !is_empty && !overwrite {
+        return Ok(false);
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, text)?;
+    Ok(true)
+}
+
+/// Adds an answer to the answers stored next to an example.
+fn store_answer(path: &PathBuf, part: u8, answer: &str) -> io::Result<()> {
+    let mut stored = fs::read_to_string(path)
+        .map(|s| answers::parse(&s))
+        .unwrap_or_default();
+
+    stored.set(part, answer);
+    fs::write(path, answers::format(&stored))
+}
+
+/// Reads and analyzes the downloaded puzzle description of `id`.
+/// Returns [`None`] if the description has not been downloaded yet.
+#[must_use]
+pub fn read_analysis(id: PuzzleId) -> Option<Analysis> {
+    let puzzle_path = resolve_data_path("puzzles", id.year, &format!("{}.md", id.day)).ok()?;
+    let html = fs::read_to_string(puzzle_path).ok()?;
+    Some(analyze(&html))
+}
+
+/// Writes the examples found in the puzzle description of `id` to `data/<year>/examples/`.
+/// Existing examples are only replaced if blocks were picked explicitly.
+pub fn write_examples(id: PuzzleId, analysis: &Analysis, pick: Pick) -> io::Result<()> {
+    let day = id.day;
+
+    let part_1 = choose(analysis, 1, pick.part_1);
+    let part_2 =
+        choose(analysis, 2, pick.part_2).filter(|c| part_1.is_none_or(|p| p.text != c.text));
+
+    if analysis.candidates.len() > 1 {
+        println!("Found {} code blocks:", analysis.candidates.len());
+
+        for c in &analysis.candidates {
+            let used = [(1, part_1), (2, part_2)]
+                .into_iter()
+                .filter(|(_, chosen)| *chosen == Some(c))
+                .map(|(part, _)| format!(" (part {part})"))
+                .collect::<String>();
+
+            println!("  #{} {}{used}", c.number, preview(&c.text));
+        }
+
+        println!("Use `--pick <part 1>[,<part 2>]` to choose other blocks.");
+    }
+
+    for (part, chosen) in [(1, part_1), (2, part_2)] {
+        let name = example_name(day, part, part_2.is_some());
+
+        let Some(answer) = analysis.answers.get(part) else {
+            continue;
+        };
+
+        // the second part shares the example of the first part if it has none of its own.
+        let Some(chosen) = chosen.or(part_1.filter(|_| part == 2)) else {
+            continue;
+        };
+
+        let example_path = get_data_path("examples", id.year, &format!("{name}.txt"));
+        let answers_path = get_data_path("examples", id.year, &format!("{name}.answers"));
+
+        let overwrite = match part {
+            1 => pick.part_1.is_some(),
+            _ => pick.part_2.is_some(),
+        };
+
+        if write_example(&example_path, &chosen.text, overwrite)? {
+            println!(
+                "Wrote example #{} to \"{}\"",
+                chosen.number,
+                example_path.display()
+            );
+        }
+
+        store_answer(&answers_path, part, answer)?;
+        println!(
+            "Stored expected answer {answer} for part {part} in \"{}\"",
+            answers_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{analyze, choose, example_name, Pick};
+    use crate::day;
+
+    const PUZZLE: &str = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Here, the calibration values are <code>12</code> and <code>38</code>.</p>
+<pre><code><em>a &lt; b</em>
+</code></pre>
+<p>Adding these together produces <code><em>50</em></code>.</p>
+</article>
+
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+</code></pre>
+<p>Adding these together produces <code><em>29</em></code>.</p>
+</article>"#;
+
+    #[test]
+    fn finds_candidates_and_answers() {
+        let analysis = analyze(PUZZLE);
+
+        assert_eq!(analysis.title.as_deref(), Some("Day 1: Trebuchet?!"));
+        assert_eq!(analysis.candidates.len(), 3);
+        assert_eq!(analysis.candidates[0].text, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(analysis.candidates[1].text, "a < b\n");
+        assert_eq!(analysis.candidates[2].part, 2);
+        assert_eq!(analysis.answers.get(1), Some("50"));
+        assert_eq!(analysis.answers.get(2), Some("29"));
+    }
+
+    #[test]
+    fn chooses_blocks() {
+        let analysis = analyze(PUZZLE);
+
+        assert_eq!(choose(&analysis, 1, None).unwrap().number, 1);
+        assert_eq!(choose(&analysis, 2, None).unwrap().number, 3);
+        assert_eq!(choose(&analysis, 1, Some(2)).unwrap().number, 2);
+        assert!(choose(&analysis, 1, Some(4)).is_none());
+    }
+
+    #[test]
+    fn names_examples() {
+        assert_eq!(example_name(day!(1), 1, false), "01");
+        assert_eq!(example_name(day!(1), 2, false), "01");
+        assert_eq!(example_name(day!(1), 1, true), "01-1");
+        assert_eq!(example_name(day!(1), 2, true), "01-2");
+    }
+
+    #[test]
+    fn parses_picks() {
+        assert_eq!(
+            "2".parse::<Pick>().unwrap(),
+            Pick {
+                part_1: Some(2),
+                part_2: None
+            }
+        );
+        assert_eq!(
+            "1,3".parse::<Pick>().unwrap(),
+            Pick {
+                part_1: Some(1),
+                part_2: Some(3)
+            }
+        );
+        assert!("0".parse::<Pick>().is_err());
+        assert!("1,2,3".parse::<Pick>().is_err());
+        assert!("a".parse::<Pick>().is_err());
+    }
+}
@@ -0,0 +1,169 @@
+/// Module that selects the input a solution binary runs against.
+///
+/// By default, a solution reads its puzzle input from `data/<year>/inputs/`. Passing
+/// `--input <path>` reads another file instead, `--input -` reads standard input and
+/// `--example [variant]` runs each part against its example from `data/<year>/examples/`.
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::{env, fs, process};
+
+use crate::template::answers::{self, Answers};
+use crate::template::{examples, read_file};
+use crate::PuzzleId;
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/<year>/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// A file passed via `--input <path>`.
+    File(PathBuf),
+    /// Standard input, passed via `--input -`.
+    Stdin,
+    /// The example `variant` of each part, passed via `--example [variant]`.
+    Example(String),
+}
+
+impl InputSource {
+    /// Parses the value of `--input`, `-` stands for standard input.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        match path {
+            "-" => Self::Stdin,
+            path => Self::File(path.into()),
+        }
+    }
+
+    /// Reads the input source from the arguments passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        Self::parse_args(&env::args().collect::<Vec<_>>())
+    }
+
+    fn parse_args(args: &[String]) -> Self {
+        if let Some(i) = args.iter().position(|x| x == "--input") {
+            return args
+                .get(i + 1)
+                .map_or_else(Self::default, |path| Self::from_path(path));
+        }
+
+        if let Some(i) = args.iter().position(|x| x == "--example") {
+            let variant = args.get(i + 1).filter(|x| !x.starts_with('-'));
+            return Self::Example(variant.cloned().unwrap_or_default());
+        }
+
+        Self::default()
+    }
+
+    /// The arguments that select this input source when passed to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(variant) if variant.is_empty() => vec!["--example".into()],
+            Self::Example(variant) => vec!["--example".into(), variant.clone()],
+        }
+    }
+
+    /// Whether this is the real puzzle input, the only input that answers can be submitted for.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    /// Reads the input for `part`. Exits the process if the input cannot be read.
+    #[must_use]
+    pub fn read(&self, id: impl Into<PuzzleId>, part: u8) -> String {
+        let id = id.into();
+
+        let result = match self {
+            Self::Puzzle => return read_file("inputs", id),
+            Self::File(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Could not read input file \"{}\": {e}", path.display())),
+            Self::Stdin => read_stdin().map_err(|e| format!("Could not read from stdin: {e}")),
+            Self::Example(variant) => examples::get(id, part, Some(variant))
+                .map(|e| e.input)
+                .ok_or_else(|| {
+                    format!(
+                        "Could not find example \"{variant}\" for part {part} of day {}.",
+                        id.day
+                    )
+                }),
+        };
+
+        result.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    /// The answers a result for this input is checked against: the known answers of the puzzle
+    /// input, the answers stored next to an example, and none for other inputs.
+    #[must_use]
+    pub fn answers(&self, id: PuzzleId, part: u8) -> Answers {
+        match self {
+            Self::Puzzle => answers::read(id),
+            Self::Example(variant) => examples::get(id, part, Some(variant))
+                .map(|e| e.answers)
+                .unwrap_or_default(),
+            Self::File(_) | Self::Stdin => Answers::default(),
+        }
+    }
+}
+
+/// Reads standard input once, both parts share the same input.
+fn read_stdin() -> io::Result<String> {
+    static STDIN: OnceLock<String> = OnceLock::new();
+
+    if let Some(input) = STDIN.get() {
+        return Ok(input.clone());
+    }
+
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(STDIN.get_or_init(|| input).clone())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn parse(args: &str) -> InputSource {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        InputSource::parse_args(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse("01 --time"), InputSource::Puzzle);
+        assert_eq!(
+            parse("01 --input data/other.txt"),
+            InputSource::File("data/other.txt".into())
+        );
+        assert_eq!(parse("01 --input -"), InputSource::Stdin);
+        assert_eq!(parse("01 --example"), InputSource::Example(String::new()));
+        assert_eq!(
+            parse("01 --example --time"),
+            InputSource::Example(String::new())
+        );
+        assert_eq!(parse("01 --example b"), InputSource::Example("b".into()));
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("inputs/big.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(String::new()),
+            InputSource::Example("b".into()),
+        ] {
+            let args = source.to_args().join(" ");
+            assert_eq!(parse(&format!("01 {args}")), source);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+/// Module that counts heap allocations of solution parts (`--mem`).
+///
+/// The template installs [`CountingAllocator`] as the global allocator of every binary. It forwards
+/// to the system allocator and only counts once [`enable`] was called, so runs without `--mem` pay
+/// for a single atomic load per allocation. Counters are kept per thread: a part is measured on the
+/// thread it runs on, allocations of threads it spawns itself are not included.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Heap usage of one run of a solution part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes requested by all allocations.
+    pub bytes: u64,
+    /// Largest amount of heap memory in use at the same time.
+    pub peak_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.bytes),
+            self.allocations
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    current: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            current: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Updates the counters of the current thread. `allocated` is the number of new bytes requested,
+/// `delta` the change of the memory in use.
+fn record(allocated: usize, delta: i64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // the counters have no destructor, so they can still be accessed while a thread shuts down.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated as u64;
+        }
+
+        c.current += delta;
+        c.peak = c.peak.max(c.current);
+        counters.set(c);
+    });
+}
+
+/// A global allocator that counts the allocations of every thread.
+pub struct CountingAllocator;
+
+#[allow(clippy::cast_possible_wrap)]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, -(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, new_size as i64 - layout.size() as i64);
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting allocations. Counting cannot be stopped again.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `func` and measures the allocations it makes on the current thread.
+/// Returns [`None`] for the memory statistics if counting is not enabled.
+pub fn track<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !is_enabled() {
+        return (func(), None);
+    }
+
+    let start = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.current;
+        counters.set(c);
+        c
+    });
+
+    let result = func();
+    let end = COUNTERS.with(Cell::get);
+
+    let stats = MemoryStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+        peak_bytes: u64::try_from(end.peak - start.current).unwrap_or_default(),
+    };
+
+    (result, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enable, format_bytes, track};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn tracks_allocations() {
+        enable();
+
+        let (sum, stats) = track(|| {
+            let first: Vec<u64> = vec![1; 1000];
+            drop(first);
+            let second: Vec<u64> = vec![2; 500];
+            second.iter().sum::<u64>()
+        });
+
+        let stats = stats.unwrap();
+        assert_eq!(sum, 1000);
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 12_000);
+        assert_eq!(stats.peak_bytes, 8000);
+    }
+}
@@ -1,43 +1,136 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod baseline;
 pub mod commands;
+pub mod examples;
+pub mod extract;
+pub mod input;
+pub mod memory;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod stats;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the path of a file in the data directory of a year, i.e. `data/<year>/<folder>/<name>`.
+#[must_use]
+pub fn get_data_path(folder: &str, year: Year, name: &str) -> PathBuf {
+    PathBuf::from("data")
+        .join(year.to_string())
+        .join(folder)
+        .join(name)
+}
+
+/// Resolves a data directory for reading.
+/// Falls back to the flat `data/<folder>` layout of single-year repositories if the data directory
+/// of the year does not exist.
+pub(crate) fn resolve_data_dir(folder: &str, year: Year) -> io::Result<PathBuf> {
+    let cwd = env::current_dir()?;
+    let dir = cwd.join("data").join(year.to_string()).join(folder);
+
+    if dir.exists() {
+        return Ok(dir);
+    }
+
+    Ok(cwd.join("data").join(folder))
+}
+
+/// Resolves a data file for reading.
+/// Falls back to the flat `data/<folder>/<name>` layout of single-year repositories if the file
+/// does not exist in the data directory of the year.
+pub(crate) fn resolve_data_path(folder: &str, year: Year, name: &str) -> io::Result<PathBuf> {
+    let cwd = env::current_dir()?;
+    let path = cwd.join(get_data_path(folder, year, name));
+
+    if path.exists() {
+        return Ok(path);
+    }
+
+    Ok(cwd.join("data").join(folder).join(name))
+}
+
+/// Returns the path of the solution binary of a puzzle.
+/// Binaries scaffolded for a specific year are named `src/bin/<year>-<day>.rs`, binaries of the
+/// default year may also be named `src/bin/<day>.rs`.
+#[must_use]
+pub fn get_bin_path(id: PuzzleId) -> PathBuf {
+    let bin_dir = Path::new("src").join("bin");
+    let path = bin_dir.join(format!("{id}.rs"));
+
+    if !path.exists() && id.year == Year::default() {
+        return bin_dir.join(format!("{}.rs", id.day));
+    }
+
+    path
+}
+
 /// Helper function that reads a text file to a string.
+/// Accepts a [`crate::Day`] of the default year or a [`PuzzleId`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, id: impl Into<PuzzleId>) -> String {
+    try_read_file(folder, id).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error instead of panicking.
+pub fn try_read_file(folder: &str, id: impl Into<PuzzleId>) -> io::Result<String> {
+    let id = id.into();
+    let filepath = resolve_data_path(folder, id.year, &format!("{}.txt", id.day))?;
+    fs::read_to_string(filepath)
 }
 
 pub fn read_custom_file(folder: &str, name: &str) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(name);
+    let filepath = resolve_data_path(folder, Year::default(), name).unwrap();
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also exposes the parts as a [`registry::Solution`] so they can be run in-process by the main binary.
+///
+/// Solutions of the default year are declared with `solution!(<day>)` and `DAY` is a [`crate::Day`].
+/// Solutions of a specific year are declared with `solution!(<year>, <day>)` and `DAY` is a [`PuzzleId`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        advent_of_code::solution!(@parts);
+    };
+    ($year:expr, $day:expr) => {
+        /// The current puzzle.
+        const DAY: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(
+            advent_of_code::year!($year),
+            advent_of_code::day!($day),
+        );
+
+        advent_of_code::solution!(@parts);
+    };
+    (@parts) => {
+        /// Entry of this solution in the registry of the main binary.
+        #[allow(dead_code)]
+        pub fn registry_entry() -> advent_of_code::template::registry::Solution {
+            advent_of_code::template::registry::Solution::new(DAY, part_one, part_two)
+        }
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let source = advent_of_code::template::input::InputSource::from_args();
+            // parts run on their own thread so they can be stopped, the input lives until the process exits.
+            let input: &'static str = source.read(DAY, 1).leak();
+            run_part(part_one, input, DAY, 1);
+            // examples can differ between parts.
+            let input: &'static str = source.read(DAY, 2).leak();
+            run_part(part_two, input, DAY, 2);
         }
     };
 }
@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::get_bin_path;
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::stats::Stats;
+use crate::{Day, PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,8 +24,11 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+    /// Heap usage of the parts if allocations were counted (`--mem`).
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
@@ -32,8 +38,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./{}", get_bin_path(id).display())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -58,26 +64,41 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn construct_table(prefix: &str, year: Year, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_memory = timings
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_cell(timing.part_1.as_ref()),
+            format_cell(timing.part_2.as_ref())
+        );
+
+        if has_memory {
+            line.push_str(&format!(
+                " `{}` | `{}` |",
+                format_memory_cell(timing.part_1_memory.as_ref()),
+                format_memory_cell(timing.part_2_memory.as_ref())
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -87,44 +108,91 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Formats the timing of a part as `median ± standard deviation`.
+fn format_cell(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) if stats.samples > 1 => {
+            format!("{:.1?} ± {:.1?}", stats.median, stats.std_dev)
+        }
+        Some(stats) => format!("{:.1?}", stats.median),
+        None => "-".into(),
+    }
+}
+
+/// Formats the heap usage of a part as `peak / allocated (allocations)`.
+fn format_memory_cell(memory: Option<&MemoryStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "{} / {} ({} allocs)",
+            format_bytes(memory.peak_bytes),
+            format_bytes(memory.bytes),
+            memory.allocations
+        ),
+        None => "-".into(),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Replaces the benchmark table in the readme with the timings of the solutions of `year`.
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::template::memory::MemoryStats;
+    use crate::template::stats::Stats;
+    use crate::{day, Year};
+
+    fn mock_stats(median_millis: u64) -> Option<Stats> {
+        let mut stats = Stats::from_duration(Duration::from_millis(median_millis));
+        stats.samples = 100;
+        stats.std_dev = Duration::from_micros(500);
+        Some(stats)
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                part_1: mock_stats(10),
+                part_2: mock_stats(20),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                part_1: mock_stats(30),
+                part_2: mock_stats(40),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                part_1: mock_stats(40),
+                part_2: mock_stats(50),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -134,28 +202,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +231,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -172,9 +240,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms ± 500.0µs` | `20.0ms ± 500.0µs` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms ± 500.0µs` | `40.0ms ± 500.0µs` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms ± 500.0µs` | `50.0ms ± 500.0µs` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +251,22 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some(MemoryStats {
+            allocations: 12,
+            bytes: 3 * 1024 * 1024,
+            peak_bytes: 1536,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::default(), timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms ± 500.0µs` | `20.0ms ± 500.0µs` | `1.5 KiB / 3.0 MiB (12 allocs)` | `-` |"
+        ));
+    }
 }
@@ -0,0 +1,220 @@
+/// Registry of solutions that are compiled into the main binary, so they can be run in-process.
+/// The list of solutions is generated by `build.rs` from the files in `src/bin/`.
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::template::runner::{self, Measurement, PartOutput};
+use crate::PuzzleId;
+
+type PartRunner = Box<dyn Fn(&str, bool, Option<Duration>) -> Measurement + Send + Sync>;
+
+/// Both parts of the solution for one puzzle.
+pub struct Solution {
+    pub id: PuzzleId,
+    part_one: PartRunner,
+    part_two: PartRunner,
+}
+
+impl Solution {
+    pub fn new<A: PartOutput, B: PartOutput>(
+        id: impl Into<PuzzleId>,
+        part_one: fn(&str) -> A,
+        part_two: fn(&str) -> B,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            part_one: Box::new(move |input, timed, timeout| {
+                let run = move |input: Arc<str>| part_one(&input);
+                runner::measure(run, Arc::from(input), timed, timeout)
+            }),
+            part_two: Box::new(move |input, timed, timeout| {
+                let run = move |input: Arc<str>| part_two(&input);
+                runner::measure(run, Arc::from(input), timed, timeout)
+            }),
+        }
+    }
+
+    /// Runs one part of the solution against `input`. If `timed` is set, the part is benched.
+    /// If a `timeout` is set, the part is abandoned once it runs longer.
+    /// Returns [`None`] if `part` is neither 1 nor 2.
+    pub fn run_part(
+        &self,
+        part: u8,
+        input: &str,
+        timed: bool,
+        timeout: Option<Duration>,
+    ) -> Option<Measurement> {
+        match part {
+            1 => Some((self.part_one)(input, timed, timeout)),
+            2 => Some((self.part_two)(input, timed, timeout)),
+            _ => None,
+        }
+    }
+}
+
+/// All solutions that are linked into the current binary.
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<Solution>,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new(mut solutions: Vec<Solution>) -> Self {
+        solutions.sort_by_key(|s| s.id);
+        Self { solutions }
+    }
+
+    #[must_use]
+    pub fn get(&self, id: impl Into<PuzzleId>) -> Option<&Solution> {
+        let id = id.into();
+        self.solutions.iter().find(|s| s.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Solution> {
+        self.solutions.iter()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::{Registry, Solution};
+    use crate::template::runner::Failure;
+    use crate::{day, PuzzleError};
+
+    fn part_one(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn part_two(_input: &str) -> Option<String> {
+        None
+    }
+
+    fn panics(_input: &str) -> Option<u32> {
+        unreachable!("no solution")
+    }
+
+    fn fails(input: &str) -> Result<u32, PuzzleError> {
+        Err(PuzzleError::at_offset(input, 1, "unexpected 'b'")
+            .with_source(PuzzleError::new("expected a digit")))
+    }
+
+    fn parses(input: &str) -> Result<u32, std::num::ParseIntError> {
+        input.parse()
+    }
+
+    fn hangs(_input: &str) -> Option<u32> {
+        std::thread::sleep(Duration::from_secs(5));
+        None
+    }
+
+    /// Succeeds once, then hangs, e.g. because it depends on state left behind by the first run.
+    fn hangs_when_benched(_input: &str) -> Option<u32> {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        if RUNS.fetch_add(1, Ordering::Relaxed) > 0 {
+            std::thread::sleep(Duration::from_secs(5));
+        }
+        Some(1)
+    }
+
+    /// Succeeds once, then panics.
+    fn panics_when_benched(_input: &str) -> Option<u32> {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        assert_eq!(RUNS.fetch_add(1, Ordering::Relaxed), 0, "ran twice");
+        Some(1)
+    }
+
+    #[test]
+    fn runs_registered_parts() {
+        let registry = Registry::new(vec![Solution::new(day!(3), part_one, part_two)]);
+        let solution = registry.get(day!(3)).unwrap();
+
+        let measurement = solution.run_part(1, "abc", false, None).unwrap();
+        assert_eq!(measurement.answer.as_deref(), Some("3"));
+        assert_eq!(measurement.stats.samples, 1);
+
+        assert_eq!(
+            solution.run_part(2, "abc", false, None).unwrap().answer,
+            None
+        );
+        assert!(solution.run_part(3, "abc", false, None).is_none());
+        assert!(registry.get(day!(4)).is_none());
+    }
+
+    #[test]
+    fn benches_registered_parts() {
+        let registry = Registry::new(vec![Solution::new(day!(1), part_one, part_two)]);
+        let measurement = registry
+            .get(day!(1))
+            .unwrap()
+            .run_part(1, "abc", true, None)
+            .unwrap();
+        assert!(measurement.stats.samples >= 10);
+    }
+
+    #[test]
+    fn captures_panics() {
+        let solution = Solution::new(day!(5), panics, part_two);
+        let measurement = solution.run_part(1, "abc", true, None).unwrap();
+
+        assert_eq!(measurement.answer, None);
+        assert_eq!(
+            measurement.failure,
+            Some(Failure::Panicked(
+                "internal error: entered unreachable code: no solution".into()
+            ))
+        );
+        assert_eq!(measurement.stats.samples, 1);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let solution = Solution::new(day!(7), parses, fails);
+
+        let measurement = solution.run_part(1, "42", false, None).unwrap();
+        assert_eq!(measurement.answer.as_deref(), Some("42"));
+        assert_eq!(measurement.failure, None);
+
+        let measurement = solution.run_part(2, "abc", true, None).unwrap();
+        assert_eq!(measurement.answer, None);
+        assert_eq!(
+            measurement.failure,
+            Some(Failure::Error(
+                "line 1, column 2: unexpected 'b'\n    abc\n     ^\n  caused by: expected a digit"
+                    .into()
+            ))
+        );
+        assert_eq!(measurement.stats.samples, 1);
+    }
+
+    #[test]
+    fn stops_parts_after_timeout() {
+        let timeout = Duration::from_millis(50);
+        let solution = Solution::new(day!(6), hangs, part_two);
+        let measurement = solution.run_part(1, "abc", false, Some(timeout)).unwrap();
+
+        assert_eq!(measurement.answer, None);
+        assert_eq!(measurement.failure, Some(Failure::Timeout(timeout)));
+    }
+
+    #[test]
+    fn guards_benched_runs() {
+        let solution = Solution::new(day!(8), panics_when_benched, hangs_when_benched);
+
+        let measurement = solution.run_part(1, "abc", true, None).unwrap();
+        assert_eq!(measurement.answer, None);
+        assert!(
+            matches!(measurement.failure, Some(Failure::Panicked(m)) if m.contains("ran twice"))
+        );
+
+        let timeout = Duration::from_millis(50);
+        let measurement = solution.run_part(2, "abc", true, Some(timeout)).unwrap();
+        assert_eq!(measurement.answer, None);
+        assert_eq!(measurement.failure, Some(Failure::Timeout(timeout)));
+    }
+}
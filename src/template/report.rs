@@ -0,0 +1,216 @@
+/// Module that describes the machine-readable output of solutions (`--format json`).
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::memory::MemoryStats;
+use crate::template::stats::Stats;
+
+/// The format solution results are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the output format from the `--format` argument passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => f.write_str("text"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of either \"text\" or \"json\"")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of running a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Solved,
+    Unsolved,
+    Timeout,
+    Panicked,
+    Error,
+}
+
+/// Execution time statistics of a solution part, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DurationReport {
+    pub mean_ns: u64,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+    pub std_dev_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+}
+
+/// A structured record of running one part of a solution, emitted as one JSON line per part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Whether the answer matches the known answer in `data/<year>/answers/`, if one was recorded.
+    #[serde(default)]
+    pub correct: Option<bool>,
+    pub status: Status,
+    /// The panic message or error chain if the part panicked or returned an error.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Heap usage of the part if it was run with `--mem`.
+    #[serde(default)]
+    pub memory: Option<MemoryStats>,
+    pub samples: u128,
+    pub duration: DurationReport,
+}
+
+/// The aggregated results of running all solutions (`cargo all --format json`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub parts: Vec<PartReport>,
+    pub total_nanos: f64,
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn duration_to_nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+impl From<&Stats> for DurationReport {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            mean_ns: duration_to_nanos(stats.mean),
+            median_ns: duration_to_nanos(stats.median),
+            min_ns: duration_to_nanos(stats.min),
+            max_ns: duration_to_nanos(stats.max),
+            std_dev_ns: duration_to_nanos(stats.std_dev),
+            p95_ns: duration_to_nanos(stats.p95),
+            p99_ns: duration_to_nanos(stats.p99),
+        }
+    }
+}
+
+impl DurationReport {
+    /// Converts the report back into [`Stats`] collected over `samples` runs.
+    #[must_use]
+    pub fn to_stats(&self, samples: u128) -> Stats {
+        Stats {
+            samples,
+            mean: Duration::from_nanos(self.mean_ns),
+            median: Duration::from_nanos(self.median_ns),
+            min: Duration::from_nanos(self.min_ns),
+            max: Duration::from_nanos(self.max_ns),
+            std_dev: Duration::from_nanos(self.std_dev_ns),
+            p95: Duration::from_nanos(self.p95_ns),
+            p99: Duration::from_nanos(self.p99_ns),
+        }
+    }
+}
+
+impl PartReport {
+    /// Converts the timing information of the record back into [`Stats`].
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        self.duration.to_stats(self.samples)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{OutputFormat, PartReport, Status};
+    use crate::template::stats::Stats;
+
+    #[test]
+    fn parse_output_format() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn part_report_roundtrip() {
+        let stats = Stats::from_samples(&[
+            Duration::from_nanos(10),
+            Duration::from_nanos(20),
+            Duration::from_nanos(30),
+        ])
+        .unwrap();
+
+        let report = PartReport {
+            year: 2023,
+            day: 1,
+            part: 2,
+            answer: Some("42".into()),
+            correct: Some(true),
+            status: Status::Solved,
+            error: None,
+            samples: stats.samples,
+            memory: None,
+            duration: (&stats).into(),
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""status":"solved""#));
+        assert!(json.contains(r#""median_ns":20"#));
+
+        let parsed: PartReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.stats(), stats);
+    }
+
+    #[test]
+    fn part_report_with_panic() {
+        let json = r#"{"year":2023,"day":1,"part":1,"answer":null,"correct":null,"status":"panicked","error":"boom","samples":1,"duration":{"mean_ns":0,"median_ns":0,"min_ns":0,"max_ns":0,"std_dev_ns":0,"p95_ns":0,"p99_ns":0}}"#;
+
+        let parsed: PartReport = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.status, Status::Panicked);
+        assert_eq!(parsed.error.as_deref(), Some("boom"));
+    }
+}
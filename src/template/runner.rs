@@ -1,63 +1,383 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::aoc_client::{self, AocClientError, SubmissionOutcome};
+use crate::template::input::InputSource;
+use crate::template::memory::{self, MemoryStats};
+use crate::template::report::{OutputFormat, PartReport, Status};
+use crate::template::{answers, stats::Stats, submissions, ANSI_ITALIC, ANSI_RESET};
+use crate::PuzzleId;
+use std::any::Any;
+use std::error::Error;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Solutions run on their own thread, give it the stack size of a main thread so deep recursion still works.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Why a solution part did not produce a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The part did not finish within the time limit.
+    Timeout(Duration),
+    /// The part panicked with the contained message.
+    Panicked(String),
+    /// The part returned an error, contains the error and its causes.
+    Error(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout(limit) => write!(f, "timeout after {limit:.1?}"),
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+            Failure::Error(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+/// The return type of a solution part: `Option<T>`, or `Result<T, E>` for parts that can explain why
+/// they failed, e.g. with a [`crate::PuzzleError`].
+pub trait PartOutput: Send + 'static {
+    type Answer: Display + Send + 'static;
+
+    /// Converts the output into an answer, or into a description of the error and its causes.
+    ///
+    /// # Errors
+    /// Returns the error chain if the part failed.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display + Send + 'static> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T, E> PartOutput for Result<T, E>
+where
+    T: Display + Send + 'static,
+    E: Into<Box<dyn Error + Send + Sync>> + Send + 'static,
+{
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| format_error_chain(&*e.into()))
+    }
+}
+
+/// Formats an error together with the errors that caused it, one per line.
+fn format_error_chain(error: &(dyn Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        message.push_str(&format!("\n  caused by: {cause}"));
+        source = cause.source();
+    }
+
+    message
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub answer: Option<String>,
+    pub stats: Stats,
+    pub failure: Option<Failure>,
+    /// Heap usage of the first run, if allocations are counted.
+    pub memory: Option<MemoryStats>,
+}
+
+impl Measurement {
+    #[must_use]
+    pub fn to_report(&self, id: PuzzleId, part: u8, correct: Option<bool>) -> PartReport {
+        PartReport {
+            year: id.year.into_inner(),
+            day: id.day.into_inner(),
+            part,
+            answer: self.answer.clone(),
+            correct,
+            status: match (&self.failure, &self.answer) {
+                (Some(Failure::Timeout(_)), _) => Status::Timeout,
+                (Some(Failure::Panicked(_)), _) => Status::Panicked,
+                (Some(Failure::Error(_)), _) => Status::Error,
+                (None, Some(_)) => Status::Solved,
+                (None, None) => Status::Unsolved,
+            },
+            error: match &self.failure {
+                Some(Failure::Panicked(message) | Failure::Error(message)) => Some(message.clone()),
+                _ => None,
+            },
+            samples: self.stats.samples,
+            duration: (&self.stats).into(),
+            memory: self.memory,
+        }
+    }
+}
+
+/// Runs one part of a solution. `id` is either a [`crate::Day`] of the default year or a [`PuzzleId`].
+/// The part is stopped after the time limit passed via `--timeout <seconds>`, its allocations are
+/// counted if `--mem` is passed. Results for inputs passed via `--input` or `--example` are never submitted.
+pub fn run_part<I, R, F>(func: F, input: I, id: impl Into<PuzzleId>, part: u8)
+where
+    I: Clone + Send + 'static,
+    R: PartOutput,
+    F: Fn(I) -> R + Send + Sync + 'static,
+{
+    let id = id.into();
     let part_str = format!("Part {part}");
+    let format = OutputFormat::from_args();
+    let timed = is_timed();
+
+    if is_mem() {
+        memory::enable();
+    }
+
+    let (result, stats, memory) = run_timed(func, input, timed, timeout_from_args(), |result| {
+        if format == OutputFormat::Text {
+            print_result(result, &part_str, "");
+
+            if timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, failure) = match result {
+        Ok(result) => (result, None),
+        Err(failure) => (None, Some(failure)),
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let measurement = Measurement {
+        answer: result.as_ref().map(ToString::to_string),
+        stats,
+        failure,
+        memory,
+    };
+    let source = InputSource::from_args();
+    let correct = answers::check(
+        &source.answers(id, part),
+        part,
+        measurement.answer.as_deref(),
+    );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match format {
+        OutputFormat::Text => print_measurement(part, &measurement, correct),
+        OutputFormat::Json => print_report(&measurement.to_report(id, part, correct)),
+    }
+
+    if let Some(result) = result.filter(|_| source.is_puzzle()) {
+        submit_result(result, id, part);
+    }
+}
+
+/// Runs a solution part without printing anything. If `timed` is set, the part is benched.
+/// If a `timeout` is set, the part is abandoned once it runs longer.
+pub fn measure<I, R, F>(func: F, input: I, timed: bool, timeout: Option<Duration>) -> Measurement
+where
+    I: Clone + Send + 'static,
+    R: PartOutput,
+    F: Fn(I) -> R + Send + Sync + 'static,
+{
+    let (result, stats, memory) = run_timed(func, input, timed, timeout, |_| {});
+
+    match result {
+        Ok(result) => Measurement {
+            answer: result.as_ref().map(ToString::to_string),
+            stats,
+            failure: None,
+            memory,
+        },
+        Err(failure) => Measurement {
+            answer: None,
+            stats,
+            failure: Some(failure),
+            memory,
+        },
+    }
+}
+
+fn is_timed() -> bool {
+    std::env::args().any(|x| x == "--time")
+}
+
+fn is_mem() -> bool {
+    std::env::args().any(|x| x == "--mem")
+}
+
+/// Reads the time limit passed via `--timeout <seconds>` to the current process.
+fn timeout_from_args() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--timeout")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| Duration::try_from_secs_f64(x.parse().ok()?).ok())
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+/// Runs `job` on a separate thread, so that a panic or an exceeded time limit does not take down
+/// the caller. A job that times out keeps running in the background until the process exits.
+fn spawn_guarded<T>(
+    job: impl FnOnce() -> T + Send + 'static,
+    timeout: Option<Duration>,
+) -> Result<T, Failure>
+where
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(job)));
+        })
+        .map_err(|e| Failure::Panicked(e.to_string()))?;
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+
+    match received {
+        Some(Ok(result)) => Ok(result),
+        Some(Err(payload)) => Err(Failure::Panicked(panic_message(&*payload))),
+        None => Err(Failure::Timeout(timeout.unwrap_or_default())),
+    }
+}
+
+/// Runs a solution part once on a separate thread, see [`spawn_guarded`].
+/// The allocations of the part are only known if it did not time out.
+fn run_guarded<I, T, F>(
+    func: Arc<F>,
+    input: I,
+    timeout: Option<Duration>,
+) -> (Result<T, Failure>, Duration, Option<MemoryStats>)
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + Sync + 'static,
+{
+    let guarded = spawn_guarded(
+        move || {
+            let timer = Instant::now();
+            // catch the panic within the tracked call, so its time and allocations are still known
+            let (result, memory) =
+                memory::track(|| panic::catch_unwind(AssertUnwindSafe(|| func(input))));
+            (result, timer.elapsed(), memory)
+        },
+        timeout,
+    );
+
+    match guarded {
+        Ok((Ok(result), elapsed, memory)) => (Ok(result), elapsed, memory),
+        Ok((Err(payload), elapsed, memory)) => (
+            Err(Failure::Panicked(panic_message(&*payload))),
+            elapsed,
+            memory,
+        ),
+        Err(Failure::Timeout(limit)) => (Err(Failure::Timeout(limit)), limit, None),
+        Err(failure) => (Err(failure), Duration::ZERO, None),
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Benching starts after the first execution succeeded. Both are guarded against panics and the time
+/// limit, which the bench may exceed by its expected duration. Allocations are only counted for the
+/// first execution.
+fn run_timed<I, R, F>(
+    func: F,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    timed: bool,
+    timeout: Option<Duration>,
+    hook: impl Fn(&Option<R::Answer>),
+) -> (
+    Result<Option<R::Answer>, Failure>,
+    Stats,
+    Option<MemoryStats>,
+)
+where
+    I: Clone + Send + 'static,
+    R: PartOutput,
+    F: Fn(I) -> R + Send + Sync + 'static,
+{
+    let func = Arc::new(func);
+    let (result, base_time, memory) = run_guarded(Arc::clone(&func), input.clone(), timeout);
+    let result = result.and_then(|output| output.into_answer().map_err(Failure::Error));
+
+    let Ok(result) = result else {
+        return (result, Stats::from_duration(base_time), memory);
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if timed {
+        let (warmup, iterations) = bench_iterations(&base_time);
+        // allow the time the bench is expected to take on top of the limit
+        let limit = timeout.map(|limit| limit + base_time * (warmup + iterations));
+        let benched = spawn_guarded(
+            move || bench(|input| func(input), input, warmup, iterations),
+            limit,
+        );
+
+        match benched {
+            Ok(stats) => stats,
+            Err(failure) => {
+                // report the limit that was passed, not the one of the whole bench
+                let failure = match failure {
+                    Failure::Timeout(_) => Failure::Timeout(timeout.unwrap_or_default()),
+                    failure => failure,
+                };
+                return (Err(failure), Stats::from_duration(base_time), memory);
+            }
+        }
     } else {
-        (base_time, 1)
+        Stats::from_duration(base_time)
     };
 
-    (result, run.0, run.1)
+    (Ok(result), stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// The number of warm-up and measured iterations to bench a part whose first execution took
+/// `base_time`: approx. 1 second of execution time or 10 samples, whatever takes longer.
+fn bench_iterations(base_time: &Duration) -> (u32, u32) {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = u32::try_from(bench_iterations).expect("clamped to at most 10000");
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    (cmp::max(bench_iterations / 10, 1), bench_iterations)
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+/// Bench a solution part. The warm-up iterations are executed and discarded before
+/// the samples used for the statistics are collected.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    warmup_iterations: u32,
+    bench_iterations: u32,
+) -> Stats {
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,26 +389,49 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: `bench_iterations` is at least 10, so there are always samples to summarise.
+    Stats::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_stats(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.mean)
+    } else {
+        format!(
+            " ({:.1?} @ {} samples, median {:.1?}, min {:.1?}, max {:.1?}, stddev {:.1?}, p95 {:.1?}, p99 {:.1?})",
+            stats.mean,
+            stats.samples,
+            stats.median,
+            stats.min,
+            stats.max,
+            stats.std_dev,
+            stats.p95,
+            stats.p99
+        )
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Prints the final result of a part, marked with ✔ or ✘ if there is a known answer.
+pub fn print_measurement(part: u8, measurement: &Measurement, correct: Option<bool>) {
+    if let Some(failure) = &measurement.failure {
+        println!("\rPart {part}: {ANSI_BOLD}{failure}{ANSI_RESET}");
+        return;
+    }
+
+    let verdict = match correct {
+        Some(true) => " ✔",
+        Some(false) => " ✘",
+        None => "",
+    };
+
+    print_result(
+        &measurement.answer,
+        &format!("Part {part}"),
+        &format!("{verdict}{}", format_stats(&measurement.stats)),
+    );
+
+    if let Some(memory) = &measurement.memory {
+        println!("  {ANSI_ITALIC}memory: {memory}{ANSI_RESET}");
     }
 }
 
@@ -127,14 +470,19 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+fn print_report(report: &PartReport) {
+    match serde_json::to_string(report) {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Failed to serialize result: {e}"),
+    }
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if `--submit <part>` is set.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    id: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +505,40 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let result = result.to_string();
+
+    let mut history = match submissions::read(id) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read submission history: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = history.check(part, &result, submissions::now()) {
+        eprintln!("Not submitting result: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(id, part, &result);
+
+    if let Ok(outcome) = &outcome {
+        history.record(part, &result, outcome, submissions::now());
+
+        if let Err(e) = submissions::write(id, &history) {
+            eprintln!("Failed to save submission history: {e}");
+        }
+    }
+
+    match &outcome {
+        Ok(SubmissionOutcome::Correct) => match answers::store(id, part, &result) {
+            Ok(()) => println!("Saved answer to \"{}\".", answers::get_path(id).display()),
+            Err(e) => eprintln!("Failed to save answer: {e}"),
+        },
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to submit result: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(outcome)
 }
@@ -0,0 +1,136 @@
+/// Module that summarises the samples collected while benchmarking a solution part.
+use std::time::Duration;
+
+/// Summary statistics of a benchmark run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+}
+
+impl Stats {
+    /// Creates [`Stats`] for a run that was executed exactly once.
+    #[must_use]
+    pub fn from_duration(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+            p99: duration,
+        }
+    }
+
+    /// Computes [`Stats`] over a set of samples, returns [`None`] if there are no samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let count = sorted.len() as u128;
+        let mean = sorted.iter().sum::<u128>() / count;
+
+        let median = if sorted.len().is_multiple_of(2) {
+            let upper = sorted.len() / 2;
+            (sorted[upper - 1] + sorted[upper]) / 2
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|&x| {
+                let delta = x as f64 - mean as f64;
+                delta * delta
+            })
+            .sum::<f64>()
+            / count as f64;
+
+        Some(Self {
+            samples: count,
+            mean: nanos_to_duration(mean),
+            median: nanos_to_duration(median),
+            min: nanos_to_duration(sorted[0]),
+            max: nanos_to_duration(sorted[sorted.len() - 1]),
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64),
+            p95: nanos_to_duration(percentile(&sorted, 95)),
+            p99: nanos_to_duration(percentile(&sorted, 99)),
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[u128], percent: usize) -> u128 {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn nanos_to_duration(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&x| Duration::from_nanos(x)).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats, Stats::from_duration(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn odd_number_of_samples() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 3, 2, 4])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+        assert_eq!(stats.p95, Duration::from_nanos(5));
+        assert_eq!(stats.p99, Duration::from_nanos(5));
+    }
+
+    #[test]
+    fn even_number_of_samples() {
+        let stats = Stats::from_samples(&nanos(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.median, Duration::from_nanos(4));
+        assert_eq!(stats.std_dev, Duration::from_nanos(2));
+    }
+
+    #[test]
+    fn percentiles() {
+        let samples: Vec<u64> = (1..=100).rev().collect();
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.p99, Duration::from_nanos(99));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+    }
+}
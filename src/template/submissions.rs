@@ -0,0 +1,314 @@
+/// Module that keeps a history of submitted answers in `data/<year>/submissions/` and guards against
+/// submissions that are known to fail: answers that were rejected before, answers outside of the
+/// bounds established by "too high" / "too low" responses and submissions during a cooldown.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::SubmissionOutcome;
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Json(e) => write!(f, "could not parse submission history: {e}"),
+            Error::IO(e) => write!(f, "could not access submission history: {e}"),
+        }
+    }
+}
+
+/// The verdict of a past submission, as stored in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix timestamp of the submission, in seconds.
+    pub submitted_at: u64,
+}
+
+/// All submissions made for one day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+    /// Unix timestamp in seconds before which the server will not accept another answer.
+    pub locked_until: Option<u64>,
+}
+
+/// Reasons to refuse a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong { verdict: Verdict },
+    TooHigh { bound: String },
+    TooLow { bound: String },
+    Cooldown { remaining: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong { verdict } => {
+                write!(f, "this answer was already rejected ({verdict:?}).")
+            }
+            Refusal::TooHigh { bound } => {
+                write!(f, "{bound} was already too high, this answer is not lower.")
+            }
+            Refusal::TooLow { bound } => {
+                write!(f, "{bound} was already too low, this answer is not higher.")
+            }
+            Refusal::Cooldown { remaining } => write!(
+                f,
+                "the server asked to wait, try again in {}s.",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+impl From<&SubmissionOutcome> for Verdict {
+    fn from(outcome: &SubmissionOutcome) -> Self {
+        match outcome {
+            SubmissionOutcome::Correct => Verdict::Correct,
+            SubmissionOutcome::Wrong { .. } => Verdict::Wrong,
+            SubmissionOutcome::TooHigh { .. } => Verdict::TooHigh,
+            SubmissionOutcome::TooLow { .. } => Verdict::TooLow,
+            SubmissionOutcome::RateLimited { .. } => Verdict::RateLimited,
+            SubmissionOutcome::WrongLevel => Verdict::WrongLevel,
+            SubmissionOutcome::Unknown => Verdict::Unknown,
+        }
+    }
+}
+
+fn wait_time(outcome: &SubmissionOutcome) -> Option<Duration> {
+    match outcome {
+        SubmissionOutcome::Wrong { wait }
+        | SubmissionOutcome::TooHigh { wait }
+        | SubmissionOutcome::TooLow { wait } => *wait,
+        SubmissionOutcome::RateLimited { wait } => Some(*wait),
+        _ => None,
+    }
+}
+
+impl History {
+    fn attempts_for(&self, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.part == part)
+    }
+
+    /// Checks whether `answer` is worth submitting for `part` at time `now` (unix seconds).
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let answer = answer.trim();
+
+        if let Some(attempt) = self
+            .attempts_for(part)
+            .find(|a| a.verdict == Verdict::Correct)
+        {
+            return Err(Refusal::AlreadySolved {
+                answer: attempt.answer.clone(),
+            });
+        }
+
+        if let Some(attempt) = self.attempts_for(part).find(|a| {
+            a.answer == answer
+                && matches!(
+                    a.verdict,
+                    Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+                )
+        }) {
+            return Err(Refusal::KnownWrong {
+                verdict: attempt.verdict,
+            });
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bounds = |verdict: Verdict| {
+                self.attempts_for(part)
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| Some((a.answer.parse::<i128>().ok()?, &a.answer)))
+            };
+
+            if let Some((_, bound)) = bounds(Verdict::TooHigh).filter(|(b, _)| value >= *b).min() {
+                return Err(Refusal::TooHigh {
+                    bound: bound.clone(),
+                });
+            }
+
+            if let Some((_, bound)) = bounds(Verdict::TooLow).filter(|(b, _)| value <= *b).max() {
+                return Err(Refusal::TooLow {
+                    bound: bound.clone(),
+                });
+            }
+        }
+
+        if let Some(locked_until) = self.locked_until.filter(|&t| t > now) {
+            return Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(locked_until - now),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Records the outcome of a submission made at time `now` (unix seconds).
+    pub fn record(&mut self, part: u8, answer: &str, outcome: &SubmissionOutcome, now: u64) {
+        self.attempts.push(Attempt {
+            part,
+            answer: answer.trim().to_string(),
+            verdict: outcome.into(),
+            submitted_at: now,
+        });
+
+        self.locked_until = wait_time(outcome).map(|wait| now + wait.as_secs());
+    }
+}
+
+#[must_use]
+pub fn get_path(id: PuzzleId) -> PathBuf {
+    get_data_path("submissions", id.year, &format!("{}.json", id.day))
+}
+
+/// Reads the submission history of a day. Returns an empty history if nothing was submitted yet.
+pub fn read(id: PuzzleId) -> Result<History, Error> {
+    match fs::read_to_string(get_path(id)) {
+        Ok(s) => Ok(serde_json::from_str(&s)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn write(id: PuzzleId, history: &History) -> Result<(), Error> {
+    let path = get_path(id);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(history)?)?;
+    Ok(())
+}
+
+/// The current time as unix timestamp in seconds.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{History, Refusal, Verdict};
+    use crate::template::aoc_client::SubmissionOutcome;
+
+    #[test]
+    fn allows_first_submission() {
+        assert_eq!(History::default().check(1, "42", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answer() {
+        let mut history = History::default();
+        history.record(1, "42", &SubmissionOutcome::Wrong { wait: None }, 0);
+
+        assert_eq!(
+            history.check(1, "42", 10),
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Wrong
+            })
+        );
+        assert_eq!(history.check(1, "43", 10), Ok(()));
+        assert_eq!(history.check(2, "42", 10), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut history = History::default();
+        history.record(1, "100", &SubmissionOutcome::TooHigh { wait: None }, 0);
+        history.record(1, "10", &SubmissionOutcome::TooLow { wait: None }, 0);
+        history.record(1, "80", &SubmissionOutcome::TooHigh { wait: None }, 0);
+
+        assert_eq!(
+            history.check(1, "90", 0),
+            Err(Refusal::TooHigh { bound: "80".into() })
+        );
+        assert_eq!(
+            history.check(1, "5", 0),
+            Err(Refusal::TooLow { bound: "10".into() })
+        );
+        assert_eq!(history.check(1, "50", 0), Ok(()));
+    }
+
+    #[test]
+    fn honors_cooldown() {
+        let mut history = History::default();
+        history.record(
+            1,
+            "42",
+            &SubmissionOutcome::Wrong {
+                wait: Some(Duration::from_secs(60)),
+            },
+            1000,
+        );
+
+        assert_eq!(
+            history.check(1, "43", 1030),
+            Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(30)
+            })
+        );
+        assert_eq!(history.check(1, "43", 1060), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_part() {
+        let mut history = History::default();
+        history.record(2, "7", &SubmissionOutcome::Correct, 0);
+
+        assert_eq!(
+            history.check(2, "8", 0),
+            Err(Refusal::AlreadySolved { answer: "7".into() })
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A year of advent of code (i.e. 2015 or later).
+///
+/// # Default
+/// The default year is read from the `AOC_YEAR` environment variable at runtime, then at compile time.
+/// If it is not set, the year of the most recent event is used.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year of the most recent event, based on the system clock.
+    fn latest_event() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() / 86_400);
+
+        // civil-from-days, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+
+        let event_year = if month == 12 { year } else { year - 1 };
+        Self::new(u16::try_from(event_year).unwrap_or(u16::MAX)).unwrap_or(Self(2015))
+    }
+}
+
+impl Default for Year {
+    fn default() -> Self {
+        std::env::var("AOC_YEAR")
+            .ok()
+            .or(option_env!("AOC_YEAR").map(String::from))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(Self::latest_event)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parse_year() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn latest_event_is_valid() {
+        assert!(Year::latest_event() >= Year(2023));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
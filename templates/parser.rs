@@ -0,0 +1,53 @@
+//! {{title}}
+advent_of_code::solution!({{solution_args}});
+
+use nom::{
+    character::complete::{line_ending, not_line_ending},
+    multi::separated_list1,
+    IResult,
+};
+
+fn parse_line(input: &str) -> IResult<&str, &str> {
+    not_line_ending(input)
+}
+
+fn parse(input: &str) -> Vec<&str> {
+    let (_, lines) = separated_list1(line_ending, parse_line)(input).expect("invalid input");
+    lines
+}
+
+pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    let _lines = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    let _lines = parse(input);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::template::examples;
+
+    // The answers of the examples, fill them in if they were not found in the puzzle description.
+    const PART_ONE: Option<&str> = {{part_one_answer}};
+    const PART_TWO: Option<&str> = {{part_two_answer}};
+
+    #[test]
+    fn test_part_one() {
+        let example = examples::get(DAY, 1, None).expect("no example for part 1");
+        let expected = PART_ONE.expect("no expected answer for part 1");
+        let result = part_one(&example.input).map(|x| x.to_string());
+        assert_eq!(result.as_deref(), Some(expected), "{}", example.name);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let example = examples::get(DAY, 2, None).expect("no example for part 2");
+        let expected = PART_TWO.expect("no expected answer for part 2");
+        let result = part_two(&example.input).map(|x| x.to_string());
+        assert_eq!(result.as_deref(), Some(expected), "{}", example.name);
+    }
+}